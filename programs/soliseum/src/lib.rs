@@ -2,15 +2,93 @@
 #![allow(unexpected_cfgs)]
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
 use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
 use anchor_lang::solana_program::system_instruction;
 use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
 declare_id!("DSabgEbjSc4ZYGL8ZkCoFiE9NFZgF1vGRmrsFFkBZiXz");
 
 pub const BPS_DENOMINATOR: u64 = 10_000;
-pub const MAX_ORACLES: usize = 3;
-pub const ORACLE_THRESHOLD: u8 = 2; // 2-of-3 multisig
+pub const MAX_FEE_BPS: u16 = BPS_DENOMINATOR as u16;
+pub const MAX_FEE_TIERS: usize = 4;
+pub const MAX_DISTRIBUTION_CHUNK: usize = 8;
+pub const MAX_CLAIM_BATCH: usize = 6;
+pub const MAX_SETTLE_BATCH: usize = 4;
+pub const MAX_SWEEP_BATCH: usize = 8;
+pub const MAX_ORACLES: usize = 7;
+pub const MAX_RELAYERS: usize = 4;
+pub const MAX_TOURNAMENT_ROUNDS: usize = 8;
+pub const MAX_OUTCOMES: usize = 8;
+/// `Arena::winner` marker for a draw / no-contest; every stake is refunded without fee
+pub const DRAW: u8 = u8::MAX;
+
+/// Typed view of a side / winner byte. Accounts, instruction args and oracle messages keep
+/// the raw `u8` (see `to_u8`), so layouts and signatures are unchanged; handlers parse it
+/// through here instead of comparing against `outcomes` and `DRAW` by hand. It is never
+/// serialized, since its borsh form would differ from the byte stored on chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// Side `n` of the arena; sides 0 and 1 are agents A and B
+    Side(u8),
+    /// No contest, every stake is refunded
+    Draw,
+}
+
+impl Outcome {
+    pub const AGENT_A: Outcome = Outcome::Side(0);
+    pub const AGENT_B: Outcome = Outcome::Side(1);
+
+    /// Parses a settled winner for an arena with `outcomes` sides; `None` if out of range.
+    pub fn from_winner(raw: u8, outcomes: u8) -> Option<Self> {
+        match raw {
+            DRAW => Some(Outcome::Draw),
+            side if side < outcomes => Some(Outcome::Side(side)),
+            _ => None,
+        }
+    }
+
+    /// Parses a side a stake can back or an oracle can name as winner; never a draw.
+    pub fn from_side(raw: u8, outcomes: u8) -> Option<Self> {
+        Self::from_winner(raw, outcomes).filter(|outcome| *outcome != Outcome::Draw)
+    }
+
+    pub fn to_u8(self) -> u8 {
+        match self {
+            Outcome::Side(side) => side,
+            Outcome::Draw => DRAW,
+        }
+    }
+}
+
+/// Account schema versions; bumped whenever a layout changes, with `migrate_arena` moving
+/// arenas forward. Each arena version names the layout ending at a given field:
+/// - v1: up to `vault_rent_reserve`. The version byte arrived well before that field, and
+///   builds between stamped 1 on shorter layouts, which cannot be migrated.
+/// - v2: up to `min_stake_lock_secs`, adding the fields from `result_hash`.
+/// - v3: up to `epoch`, adding the fields from `min_fee_lamports`.
+/// - v4: `next_side_index` appended.
+///
+/// Stake v2 added `claimed_amount`, `placed_at` and `epoch`, and moved stakes to per-round
+/// seeds. A v1 stake cannot be migrated in place since its address changes with the seeds,
+/// so a deployment with open v1 stakes must be redeployed with fresh state.
+pub const ARENA_VERSION: u8 = 4;
+pub const STAKE_VERSION: u8 = 2;
+
+/// Byte offsets `migrate_arena` reads from a raw arena: the committee length (after the
+/// discriminator, version, creator and arena_id), and `outcomes` minus the committee
+/// members (after the length prefix, threshold and total_pool)
+const ARENA_ORACLES_OFFSET: usize = 8 + 1 + 32 + 8;
+const ARENA_OUTCOMES_OFFSET_BASE: usize = ARENA_ORACLES_OFFSET + 4 + 1 + 8;
+
+/// Violation bits reported by `check_invariants`
+pub const INVARIANT_POOL_MISMATCH: u8 = 1 << 0;
+pub const INVARIANT_VAULT_UNDERFUNDED: u8 = 1 << 1;
+pub const INVARIANT_WINNER_MISMATCH: u8 = 1 << 2;
+pub const INVARIANT_UNCLAIMED_MISMATCH: u8 = 1 << 3;
 
 /// Arena lifecycle status
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -19,6 +97,7 @@ pub enum ArenaStatus {
     Active,
     Settled,
     Cancelled,
+    Disputable, // Settled, but claims wait out the challenge window (see finalize_settlement)
 }
 
 impl Default for ArenaStatus {
@@ -27,43 +106,282 @@ impl Default for ArenaStatus {
     }
 }
 
+/// How a winning side splits the net loser pool among its stakes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PayoutMode {
+    Proportional, // Share by stake amount
+    Quadratic,    // Share by isqrt(stake amount), damping whale stakes
+}
+
+impl Default for PayoutMode {
+    fn default() -> Self {
+        PayoutMode::Proportional
+    }
+}
+
+/// Who may declare the winner in `settle_game`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SettlementMode {
+    OracleCommittee, // A threshold of committee signatures, submitted by a member
+    CreatorOnly,     // The creator's own transaction; stakers trust the creator alone
+}
+
+impl Default for SettlementMode {
+    fn default() -> Self {
+        SettlementMode::OracleCommittee
+    }
+}
+
+/// Result of `try_claim`, returned instead of an error when there is nothing to pay
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClaimStatus {
+    /// Paid out this many lamports
+    Claimed(u64),
+    AlreadyClaimed,
+    /// The arena is not Settled yet (or still Disputable)
+    NotSettled,
+    /// The stake is on a losing side
+    NotAWinner,
+}
+
+/// One-call snapshot of an arena for dashboards, returned by `get_arena_summary`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ArenaSummary {
+    pub status: ArenaStatus,
+    pub paused: bool,
+    pub pools: Vec<u64>,
+    pub total_pool: u64,
+    pub total_stakers: u32,
+    pub odds_bps: Vec<u16>, // As returned by `get_odds`
+    pub winner: Option<u8>,
+    pub settlement_nonce: u64,
+    pub created_at: i64,
+    pub betting_opens_at: i64,
+    pub betting_closes_at: i64,
+    pub settled_at: i64,
+    pub claim_deadline: i64,
+}
+
+/// Volume-scaled fee: pools of at least `threshold` lamports pay `fee_bps`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct FeeTier {
+    pub threshold: u64,
+    pub fee_bps: u16,
+}
+
+impl FeeTier {
+    pub const LEN: usize = 8 + 2;
+}
+
 /// Oracle signature for multisig settlement
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
 pub struct OracleSignature {
-    pub oracle_index: u8, // 0, 1, or 2
+    pub oracle_index: u8, // Position in the arena's committee
     pub signature: [u8; 64], // Ed25519 signature
 }
 
+/// One arena's result in `settle_games_batch`, matching the `settle_game` arguments
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchSettlement {
+    pub winner: u8,
+    pub oracle_signatures: Vec<OracleSignature>,
+    pub outcome_hash: Option<[u8; 32]>,
+    pub expected_nonce: u64,
+}
+
 #[program]
 pub mod soliseum {
     use super::*;
 
     /// Initialize a new arena with oracle committee and platform fee configuration.
-    /// Requires exactly 3 oracle pubkeys for 2-of-3 multisig.
-    pub fn initialize_arena(
-        ctx: Context<InitializeArena>,
+    /// `arena_id` is part of the arena PDA seeds so one creator can run many arenas at once.
+    /// `oracle_pubkeys` is a committee of 1 to MAX_ORACLES distinct keys, of which
+    /// `oracle_threshold` must sign settlement and other committee actions.
+    /// `outcomes` (2 to MAX_OUTCOMES) is the number of sides stakes and settlement may name.
+    /// `stake_increment` snaps stakes to multiples of that many lamports (0 = any amount).
+    /// `fee_tiers` (ascending thresholds) override `fee_bps` once the pool reaches a threshold.
+    /// Fees go up to MAX_FEE_BPS: at a 100% fee the whole loser pool is fee and winners get
+    /// exactly their stake back.
+    /// With `payout_mint` set, winners are paid in that token at `payout_rate` base units per
+    /// SOL of payout, from a pre-funded token vault, instead of in lamports.
+    /// `dispute_window_secs` opens a post-settlement window for `correct_winner` (0 = none).
+    /// `fee_recipient` receives vault residue on `expire_arena`, allowed `claim_expiry_secs`
+    /// after settlement.
+    /// With `stake_mint` set, stakes and payouts are in that token via the `*_spl`
+    /// instructions and the native SOL ones are rejected.
+    /// Stakes are only accepted within `[betting_opens_at, betting_closes_at)` and settlement
+    /// waits for `betting_closes_at`; 0 leaves that end of the window unbounded.
+    /// `min_stake` bounds each stake from below and `max_stake_per_user` caps a user's
+    /// total stake; 0 disables either check.
+    /// `agent_a_name`, `agent_b_name` (zero-padded, non-empty) and `match_id` describe the
+    /// battle for explorers.
+    /// Committee rotations take effect `oracle_update_delay_secs` after they are proposed.
+    /// `emergency_timeout_secs` (required) is how long after creation, or after betting
+    /// closes if later, an unsettled arena may be cancelled via `emergency_cancel`.
+    /// `referral_bps` (at most `fee_bps`) of the fee withheld from a referred stake's claim is
+    /// set aside for its referrer, who collects it with `claim_referral`.
+    /// `max_side_share_bps` caps any side's share of the pool a stake may leave behind
+    /// (0 = no cap).
+    /// `claim_window_secs` (SOL arenas only, 0 = none) closes claims that long after settlement;
+    /// `sweep_unclaimed` then sends whatever is left in the vault to `fee_recipient`.
+    /// `cluster_id` tags every oracle-signed message with the cluster this deployment serves,
+    /// alongside the program id, so committee signatures cannot be replayed elsewhere.
+    /// `settle_game` waits for at least `min_participants` stakers (0 = no minimum); an
+    /// arena that never reaches it can still be cancelled and refunded.
+    /// A non-zero `whitelist_root` restricts staking to the members of that Merkle allowlist
+    /// (see `verify_allowlist_proof`); zero leaves the arena open.
+    /// With `challenge_window_secs` set, settlement leaves the arena `Disputable` for that
+    /// long: claims wait for `finalize_settlement`, and `dispute_settlement` can reopen it.
+    /// `max_total_pool` caps the arena's total exposure in lamports (0 = uncapped).
+    /// Without `allow_creator_stake` the creator cannot stake, keeping the arena neutral.
+    /// `settle_game` waits `settlement_lockout_secs` after the latest stake (0 = no lockout).
+    /// `payout_mode` picks how winners split the loser pool: by stake, or by its square root.
+    /// `oracle_groups` assigns each committee seat to an operator group (`None` = every seat
+    /// its own group); `settle_game` needs signers from `min_signer_groups` groups (0 = off).
+    /// `withdraw_stake` waits `min_stake_lock_secs` after a stake is first placed (0 = no lock).
+    /// `min_fee_lamports` floors the fee taken from the loser pool (0 = percentage only).
+    /// Until every side's pool reaches `min_side_liquidity` the arena is in matchmaking:
+    /// stakes are taken but `settle_game` fails with `MarketNotReady` (0 = off).
+    /// `max_resets` caps how often `reset_arena` may reopen the arena (0 = unlimited).
+    /// `settlement_mode` `CreatorOnly` lets the creator settle without oracle signatures, for
+    /// casual bets among people who trust the creator: nothing then stops a creator from
+    /// declaring whichever winner they like. Draws, disputes and resets still go through
+    /// the committee. `OracleCommittee` is the trust-minimized default.
+    /// Non-empty `relayers` (up to MAX_RELAYERS) are the only keys that may submit committee
+    /// results, so oracles can sign with cold keys and leave sending to hot ones.
+    /// `house_bonus_bps` is the share of `total_pool` the creator matches as a bonus for the
+    /// winning side, paid into the vault with `house_deposit` (0 = no bonus).
+    /// Optional `remaining_accounts`: the `GlobalConfig` PDA, to count the new arena.
+    pub fn initialize_arena<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializeArena<'info>>,
+        arena_id: u64,
         fee_bps: u16,
-        oracle_pubkeys: [Pubkey; MAX_ORACLES],
+        oracle_pubkeys: Vec<Pubkey>,
+        oracle_threshold: u8,
+        outcomes: u8,
+        stake_increment: u64,
+        fee_tiers: Vec<FeeTier>,
+        payout_mint: Option<Pubkey>,
+        payout_rate: u64,
+        dispute_window_secs: i64,
+        fee_recipient: Pubkey,
+        claim_expiry_secs: i64,
+        stake_mint: Option<Pubkey>,
+        betting_opens_at: i64,
+        betting_closes_at: i64,
+        min_stake: u64,
+        max_stake_per_user: u64,
+        agent_a_name: [u8; 32],
+        agent_b_name: [u8; 32],
+        match_id: [u8; 16],
+        oracle_update_delay_secs: i64,
+        emergency_timeout_secs: i64,
+        referral_bps: u16,
+        max_side_share_bps: u16,
+        claim_window_secs: i64,
+        cluster_id: u8,
+        min_participants: u16,
+        whitelist_root: [u8; 32],
+        challenge_window_secs: i64,
+        max_total_pool: u64,
+        allow_creator_stake: bool,
+        settlement_lockout_secs: i64,
+        payout_mode: PayoutMode,
+        oracle_groups: Option<[u8; MAX_ORACLES]>,
+        min_signer_groups: u8,
+        min_stake_lock_secs: i64,
+        min_fee_lamports: u64,
+        min_side_liquidity: u64,
+        settlement_mode: SettlementMode,
+        max_resets: u32,
+        relayers: Vec<Pubkey>,
+        house_bonus_bps: u16,
     ) -> Result<()> {
-        require!(fee_bps <= BPS_DENOMINATOR as u16, SoliseumError::MathOverflow);
+        require!(fee_bps <= MAX_FEE_BPS, SoliseumError::MathOverflow);
+        require!(fee_tiers.len() <= MAX_FEE_TIERS, SoliseumError::InvalidFeeTiers);
+        require!(
+            fee_tiers.iter().all(|tier| tier.fee_bps <= MAX_FEE_BPS),
+            SoliseumError::InvalidFeeTiers
+        );
+        require!(
+            fee_tiers.windows(2).all(|pair| pair[0].threshold < pair[1].threshold),
+            SoliseumError::InvalidFeeTiers
+        );
+        require!(
+            payout_mint.is_none() || payout_rate > 0,
+            SoliseumError::InvalidPayoutConfig
+        );
+        require!(
+            stake_mint.is_none() || payout_mint.is_none(),
+            SoliseumError::InvalidPayoutConfig
+        );
+        require!(dispute_window_secs >= 0, SoliseumError::InvalidArenaState);
+        require!(claim_expiry_secs >= 0, SoliseumError::InvalidArenaState);
+        require!(oracle_update_delay_secs >= 0, SoliseumError::InvalidArenaState);
+        require!(emergency_timeout_secs > 0, SoliseumError::InvalidArenaState);
+        require!(referral_bps <= fee_bps, SoliseumError::InvalidReferralConfig);
+        require!(max_side_share_bps as u64 <= BPS_DENOMINATOR, SoliseumError::InvalidArenaState);
+        require!(house_bonus_bps as u64 <= BPS_DENOMINATOR, SoliseumError::InvalidArenaState);
+        // The deadline must not cut into the dispute window, and only the SOL vault is swept
+        require!(
+            claim_window_secs == 0 || claim_window_secs >= dispute_window_secs,
+            SoliseumError::InvalidArenaState
+        );
+        require!(claim_window_secs >= 0, SoliseumError::InvalidArenaState);
+        require!(challenge_window_secs >= 0, SoliseumError::InvalidArenaState);
+        require!(settlement_lockout_secs >= 0, SoliseumError::InvalidArenaState);
+        require!(min_stake_lock_secs >= 0, SoliseumError::InvalidArenaState);
         require!(
-            oracle_pubkeys.iter().all(|pk| *pk != Pubkey::default()),
+            min_signer_groups as usize <= oracle_pubkeys.len(),
             SoliseumError::InvalidOracleConfig
         );
-        // Ensure all oracles are unique
-        for i in 0..MAX_ORACLES {
-            for j in (i + 1)..MAX_ORACLES {
-                require!(
-                    oracle_pubkeys[i] != oracle_pubkeys[j],
-                    SoliseumError::InvalidOracleConfig
-                );
-            }
-        }
+        require!(
+            claim_window_secs == 0 || claim_window_secs > challenge_window_secs,
+            SoliseumError::InvalidArenaState
+        );
+        require!(
+            claim_window_secs == 0 || (stake_mint.is_none() && payout_mint.is_none()),
+            SoliseumError::InvalidPayoutConfig
+        );
+        require!(fee_recipient != Pubkey::default(), SoliseumError::InvalidArenaState);
+        require!(
+            betting_opens_at == 0 || betting_closes_at == 0 || betting_opens_at < betting_closes_at,
+            SoliseumError::InvalidArenaState
+        );
+        require!(
+            max_stake_per_user == 0 || min_stake <= max_stake_per_user,
+            SoliseumError::InvalidArenaState
+        );
+        require!(
+            agent_a_name.iter().any(|b| *b != 0) && agent_b_name.iter().any(|b| *b != 0),
+            SoliseumError::InvalidAgentName
+        );
+        validate_committee(&oracle_pubkeys)?;
+        require!(
+            relayers.len() <= MAX_RELAYERS && !relayers.contains(&Pubkey::default()),
+            SoliseumError::InvalidOracleConfig
+        );
+        require!(
+            oracle_threshold >= 1 && oracle_threshold as usize <= oracle_pubkeys.len(),
+            SoliseumError::InvalidThreshold
+        );
+        require!(
+            outcomes >= 2 && outcomes as usize <= MAX_OUTCOMES,
+            SoliseumError::InvalidOutcomeCount
+        );
 
+        let arena_key = ctx.accounts.arena.key();
         let (vault_pubkey, vault_bump) = Pubkey::find_program_address(
-            &[b"vault", ctx.accounts.creator.key().as_ref()],
+            &[b"vault", arena_key.as_ref()],
             ctx.program_id,
         );
+        // The seeds constraint already checks this, but the create_account CPI
+        // below signs for vault_pubkey, so refuse to run it against anything else.
+        require_keys_eq!(
+            ctx.accounts.vault.key(),
+            vault_pubkey,
+            SoliseumError::InvalidArenaState
+        );
         let vault = &ctx.accounts.vault;
         if vault.lamports() == 0 {
             invoke_signed(
@@ -79,37 +397,136 @@ pub mod soliseum {
                     ctx.accounts.vault.to_account_info(),
                     ctx.accounts.system_program.to_account_info(),
                 ],
-                &[&[b"vault", ctx.accounts.creator.key().as_ref(), &[vault_bump]]],
+                &[&[b"vault", arena_key.as_ref(), &[vault_bump]]],
             )?;
         }
 
         let arena = &mut ctx.accounts.arena;
+        arena.version = ARENA_VERSION;
         arena.creator = ctx.accounts.creator.key();
+        arena.arena_id = arena_id;
         arena.oracles = oracle_pubkeys;
-        arena.oracle_threshold = ORACLE_THRESHOLD;
+        arena.oracle_threshold = oracle_threshold;
         arena.total_pool = 0;
-        arena.agent_a_pool = 0;
-        arena.agent_b_pool = 0;
+        arena.outcomes = outcomes;
+        arena.pools = vec![0; outcomes as usize];
         arena.status = ArenaStatus::Active;
         arena.winner = None;
         arena.fee_bps = fee_bps;
         arena.settlement_nonce = 0;
+        arena.stakers = vec![0; outcomes as usize];
+        arena.next_side_index = vec![0; outcomes as usize];
+        arena.seeded = vec![0; outcomes as usize];
+        arena.unclaimed_count = 0;
+        arena.stake_increment = stake_increment;
+        arena.fee_tiers = fee_tiers;
+        arena.distribution_cursor = 0;
+        arena.fully_distributed = false;
+        arena.payout_mint = payout_mint;
+        arena.payout_rate = payout_rate;
+        arena.dispute_window_secs = dispute_window_secs;
+        arena.settled_at = 0;
+        arena.fee_recipient = fee_recipient;
+        arena.claim_expiry_secs = claim_expiry_secs;
+        arena.stake_mint = stake_mint;
+        arena.betting_opens_at = betting_opens_at;
+        arena.betting_closes_at = betting_closes_at;
+        arena.accrued_fees = 0;
+        arena.fees_withdrawn = 0;
+        arena.min_stake = min_stake;
+        arena.max_stake_per_user = max_stake_per_user;
+        arena.agent_a_name = agent_a_name;
+        arena.agent_b_name = agent_b_name;
+        arena.match_id = match_id;
+        arena.paused = false;
+        arena.oracle_update_delay_secs = oracle_update_delay_secs;
+        arena.created_at = Clock::get()?.unix_timestamp;
+        arena.emergency_timeout_secs = emergency_timeout_secs;
+        arena.referral_bps = referral_bps;
+        arena.referral_owed = 0;
+        arena.settled_winner_pool = 0;
+        arena.settled_net_loser_pool = 0;
+        arena.settled_loser_pool = 0;
+        arena.settled_fee_bps = 0;
+        arena.vault_bump = vault_bump;
+        arena.max_side_share_bps = max_side_share_bps;
+        arena.claim_window_secs = claim_window_secs;
+        arena.claim_deadline = 0;
+        arena.cluster_id = cluster_id;
+        arena.min_participants = min_participants;
+        arena.whitelist_root = whitelist_root;
+        arena.challenge_window_secs = challenge_window_secs;
+        arena.max_total_pool = max_total_pool;
+        arena.vault_rent_reserve = 0;
+        arena.result_hash = [0; 32];
+        arena.allow_creator_stake = allow_creator_stake;
+        arena.settlement_lockout_secs = settlement_lockout_secs;
+        arena.last_stake_at = 0;
+        arena.payout_mode = payout_mode;
+        arena.sqrt_pools = vec![0; outcomes as usize];
+        arena.settled_sqrt_pool = 0;
+        arena.oracle_groups = oracle_groups.unwrap_or(core::array::from_fn(|i| i as u8));
+        arena.min_signer_groups = min_signer_groups;
+        arena.min_stake_lock_secs = min_stake_lock_secs;
+        arena.min_fee_lamports = min_fee_lamports;
+        arena.min_side_liquidity = min_side_liquidity;
+        arena.settlement_mode = settlement_mode;
+        arena.max_resets = max_resets;
+        arena.reset_count = 0;
+        arena.relayers = relayers;
+        arena.house_bonus_bps = house_bonus_bps;
+        arena.house_bonus = 0;
+        arena.epoch = 0;
+        arena.pending_oracles = Vec::new();
+        arena.oracle_update_effective_at = 0;
+
+        emit!(ArenaInitialized {
+            arena: ctx.accounts.arena.key(),
+            creator: ctx.accounts.creator.key(),
+            fee_bps,
+            oracles: ctx.accounts.arena.oracles.clone(),
+            oracle_threshold,
+            agent_a_name,
+            agent_b_name,
+            match_id,
+        });
+
+        record_global_stats(ctx.remaining_accounts, ctx.program_id, 1, 0, 0)?;
 
         Ok(())
     }
 
     /// Place a stake on an agent. Only allowed when arena status is Active.
-    pub fn place_stake(
-        ctx: Context<PlaceStake>,
+    /// `min_acceptable_odds_bps` is slippage protection: the stake reverts with `OddsSlippage`
+    /// if the side's implied payout multiplier after this stake (10_000 = 1x, before fees)
+    /// has fallen below it. `max_implied_prob_bps` guards the same risk from the other end:
+    /// the stake reverts with `OddsSlippageExceeded` if its side's share of the pool after
+    /// this stake, its implied probability, is above it.
+    /// `referrer` credits an integrator with part of the fee on this stake's winnings; it is
+    /// fixed by the first stake and may not be the staker.
+    /// `allowlist_proof` is required on arenas with a `whitelist_root`.
+    /// Optional `remaining_accounts`: the `GlobalConfig` PDA, to add the stake to its volume.
+    pub fn place_stake<'info>(
+        ctx: Context<'_, '_, '_, 'info, PlaceStake<'info>>,
         amount: u64,
         side: u8,
+        min_acceptable_odds_bps: Option<u64>,
+        referrer: Option<Pubkey>,
+        allowlist_proof: Option<Vec<[u8; 32]>>,
+        max_implied_prob_bps: Option<u16>,
     ) -> Result<()> {
-        require!(side <= 1, SoliseumError::InvalidArenaState);
-        require!(
-            ctx.accounts.arena.status == ArenaStatus::Active,
-            SoliseumError::InvalidArenaState
-        );
-        require!(amount > 0, SoliseumError::MathOverflow);
+        let user = ctx.accounts.user.key();
+        check_allowlist(&ctx.accounts.arena, &user, allowlist_proof.as_deref())?;
+        let new_total_pool = record_stake(
+            &mut ctx.accounts.arena,
+            &mut ctx.accounts.stake,
+            user,
+            amount,
+            side,
+            min_acceptable_odds_bps,
+            max_implied_prob_bps,
+            referrer,
+        )?;
 
         let cpi_accounts = Transfer {
             from: ctx.accounts.user.to_account_info(),
@@ -121,318 +538,3046 @@ pub mod soliseum {
         );
         transfer(cpi_ctx, amount)?;
 
+        emit!(VaultTransfer {
+            arena: ctx.accounts.arena.key(),
+            direction: VaultDirection::Inflow,
+            amount,
+            counterparty: user,
+            total_pool: new_total_pool,
+        });
+
+        emit!(StakePlaced {
+            arena: ctx.accounts.arena.key(),
+            user,
+            side,
+            amount,
+            pools: ctx.accounts.arena.pools.clone(),
+            stakers: ctx.accounts.arena.stakers.clone(),
+            total_pool: new_total_pool,
+        });
+
+        record_global_stats(ctx.remaining_accounts, ctx.program_id, 0, amount, 0)?;
+
+        Ok(())
+    }
+
+    /// Token-denominated `place_stake` for arenas with a `stake_mint`. Tokens move from
+    /// the user's account into the vault PDA's associated token account.
+    pub fn place_stake_spl(
+        ctx: Context<PlaceStakeSpl>,
+        amount: u64,
+        side: u8,
+        min_acceptable_odds_bps: Option<u64>,
+        allowlist_proof: Option<Vec<[u8; 32]>>,
+        max_implied_prob_bps: Option<u16>,
+    ) -> Result<()> {
+        check_allowlist(&ctx.accounts.arena, &ctx.accounts.user.key(), allowlist_proof.as_deref())?;
+        let new_total_pool = record_stake(
+            &mut ctx.accounts.arena,
+            &mut ctx.accounts.stake,
+            ctx.accounts.user.key(),
+            amount,
+            side,
+            min_acceptable_odds_bps,
+            max_implied_prob_bps,
+            None,
+        )?;
+
+        let cpi_accounts = token::Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.stake_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        emit!(StakePlaced {
+            arena: ctx.accounts.arena.key(),
+            user: ctx.accounts.user.key(),
+            side,
+            amount,
+            pools: ctx.accounts.arena.pools.clone(),
+            stakers: ctx.accounts.arena.stakers.clone(),
+            total_pool: new_total_pool,
+        });
+
+        Ok(())
+    }
+
+    /// Pull some or all of a stake back out while the arena is still Active. A fully
+    /// withdrawn stake stays open at zero so its `side_index` slot remains valid for
+    /// distribution, but leaves its side's staker count and is marked claimed, so no
+    /// settlement or cancellation waits on it; it can be topped up again on the same side.
+    /// Rejected with `StakeLocked` until `min_stake_lock_secs` after the stake was first
+    /// placed, and with `BettingClosed` once the betting window has closed.
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>, amount: u64) -> Result<()> {
+        require!(amount > 0, SoliseumError::MathOverflow);
+        let now = Clock::get()?.unix_timestamp;
+        require!(ctx.accounts.arena.betting_open(now), SoliseumError::BettingClosed);
+        require!(
+            now >= ctx.accounts.stake.placed_at.saturating_add(ctx.accounts.arena.min_stake_lock_secs),
+            SoliseumError::StakeLocked
+        );
+        require!(
+            amount <= ctx.accounts.stake.amount,
+            SoliseumError::WithdrawalExceedsStake
+        );
+        let stake_increment = ctx.accounts.arena.stake_increment;
+        require!(
+            stake_increment == 0 || amount % stake_increment == 0,
+            SoliseumError::InvalidStakeIncrement
+        );
+
+        require!(
+            amount <= ctx.accounts.vault.lamports(),
+            SoliseumError::VaultUnderfunded
+        );
+        require!(
+            amount <= ctx.accounts.arena.spendable(ctx.accounts.vault.lamports()),
+            SoliseumError::RentReserveBreached
+        );
+
+        let side = ctx.accounts.stake.side;
         let arena = &mut ctx.accounts.arena;
-        let stake = &mut ctx.accounts.stake;
-        if stake.amount == 0 {
-            stake.owner = ctx.accounts.user.key();
-            stake.amount = amount;
-            stake.side = side;
-            stake.claimed = false;
-        } else {
-            require!(stake.side == side, SoliseumError::InvalidArenaState);
-            stake.amount = stake
-                .amount
-                .checked_add(amount)
-                .ok_or(SoliseumError::MathOverflow)?;
+        arena.total_pool = arena.total_pool.checked_sub(amount).ok_or(SoliseumError::MathOverflow)?;
+        let side_pool = &mut arena.pools[side as usize];
+        *side_pool = side_pool.checked_sub(amount).ok_or(SoliseumError::MathOverflow)?;
+        let old_amount = ctx.accounts.stake.amount;
+        let new_amount = old_amount.checked_sub(amount).ok_or(SoliseumError::MathOverflow)?;
+        arena.reweigh(side, old_amount, new_amount)?;
+        if new_amount == 0 {
+            let side_stakers = &mut arena.stakers[side as usize];
+            *side_stakers = side_stakers.checked_sub(1).ok_or(SoliseumError::MathOverflow)?;
+            ctx.accounts.stake.claimed = true;
         }
+        ctx.accounts.stake.amount = new_amount;
 
-        arena.total_pool = arena.total_pool.checked_add(amount).ok_or(SoliseumError::MathOverflow)?;
-        if side == 0 {
-            arena.agent_a_pool = arena.agent_a_pool.checked_add(amount).ok_or(SoliseumError::MathOverflow)?;
-        } else {
-            arena.agent_b_pool = arena.agent_b_pool.checked_add(amount).ok_or(SoliseumError::MathOverflow)?;
-        }
+        pay_from_vault(
+            &ctx.accounts.arena,
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program,
+            amount,
+        )?;
 
         Ok(())
     }
 
     /// Reset a settled arena to Active so it can be used for another battle.
-    /// Requires 2-of-3 oracle signatures OR creator signature.
+    /// The vault must be empty, so fees and rounding dust are taken out with
+    /// `withdraw_fees` first.
+    /// Requires the creator's signature, OR a threshold of oracle signatures submitted by a
+    /// committee member or listed relayer.
+    /// The creator may also set the next round's `fee_bps` and betting window; `None` keeps
+    /// the current value. The oracle committee is carried over unchanged.
+    /// Fails with `ResetLimitReached` once `max_resets` resets have been made.
     pub fn reset_arena(
         ctx: Context<ResetArena>,
         oracle_signatures: Option<Vec<OracleSignature>>,
+        new_fee_bps: Option<u16>,
+        new_betting_opens_at: Option<i64>,
+        new_betting_closes_at: Option<i64>,
     ) -> Result<()> {
+        // Preconditions: no claim may still be possible when the pools are zeroed
+        let arena = &ctx.accounts.arena;
+        arena.require_status(ArenaStatus::Settled)?;
         require!(
-            ctx.accounts.arena.status == ArenaStatus::Settled,
-            SoliseumError::InvalidArenaState
+            !arena.in_dispute_window(Clock::get()?.unix_timestamp),
+            SoliseumError::DisputeWindowOpen
         );
+        require!(arena.unclaimed_count == 0, SoliseumError::ClaimsOutstanding);
         require!(
-            ctx.accounts.vault.lamports() == 0,
-            SoliseumError::InvalidArenaState
+            arena.spendable(ctx.accounts.vault.lamports()) == 0,
+            SoliseumError::VaultNotEmpty
+        );
+        require!(
+            arena.max_resets == 0 || arena.reset_count < arena.max_resets,
+            SoliseumError::ResetLimitReached
         );
 
-        let arena = &ctx.accounts.arena;
         let is_creator = ctx.accounts.authority.key() == arena.creator;
         
         if !is_creator {
-            // Must have oracle signatures
-            let sigs = oracle_signatures.ok_or(SoliseumError::UnauthorizedOracle)?;
             require!(
-                sigs.len() >= arena.oracle_threshold as usize,
-                SoliseumError::InsufficientSignatures
+                arena.may_relay(&ctx.accounts.authority.key()),
+                SoliseumError::UnauthorizedOracle
             );
-            
-            // Verify all signatures are from different oracles
-            let mut used_indices = Vec::new();
-            for sig in &sigs {
-                require!(
-                    !used_indices.contains(&sig.oracle_index),
-                    SoliseumError::DuplicateOracle
-                );
-                require!(
-                    sig.oracle_index < MAX_ORACLES as u8,
-                    SoliseumError::InvalidOracleIndex
-                );
-                used_indices.push(sig.oracle_index);
-                
-                // Verify signature over arena address + settlement_nonce
-                let message = create_reset_message(&ctx.accounts.arena.key(), arena.settlement_nonce);
-                require!(
-                    verify_ed25519_signature(
-                        &arena.oracles[sig.oracle_index as usize],
-                        &message,
-                        &sig.signature
-                    ),
-                    SoliseumError::InvalidSignature
-                );
-            }
+            // Must have oracle signatures over arena address + settlement_nonce
+            let sigs = oracle_signatures.ok_or(SoliseumError::UnauthorizedOracle)?;
+            let message = create_reset_message(
+                ctx.program_id,
+                arena.cluster_id,
+                &ctx.accounts.arena.key(),
+                arena.settlement_nonce,
+            );
+            verify_oracle_signatures(arena, &message, &sigs)?;
         }
 
+        // Oracles only sign the reset itself, so only the creator may change the economics
+        let reconfigures =
+            new_fee_bps.is_some() || new_betting_opens_at.is_some() || new_betting_closes_at.is_some();
+        require!(is_creator || !reconfigures, SoliseumError::InvalidArenaState);
+        let fee_bps = new_fee_bps.unwrap_or(arena.fee_bps);
+        require!(fee_bps <= MAX_FEE_BPS, SoliseumError::MathOverflow);
+        require!(arena.referral_bps <= fee_bps, SoliseumError::InvalidReferralConfig);
+        let betting_opens_at = new_betting_opens_at.unwrap_or(arena.betting_opens_at);
+        let betting_closes_at = new_betting_closes_at.unwrap_or(arena.betting_closes_at);
+        require!(
+            betting_opens_at == 0 || betting_closes_at == 0 || betting_opens_at < betting_closes_at,
+            SoliseumError::InvalidArenaState
+        );
+
         let arena = &mut ctx.accounts.arena;
+        arena.fee_bps = fee_bps;
+        arena.betting_opens_at = betting_opens_at;
+        arena.betting_closes_at = betting_closes_at;
         arena.status = ArenaStatus::Active;
         arena.winner = None;
+        arena.result_hash = [0; 32];
         arena.total_pool = 0;
-        arena.agent_a_pool = 0;
-        arena.agent_b_pool = 0;
+        arena.pools.iter_mut().for_each(|pool| *pool = 0);
+        arena.stakers.iter_mut().for_each(|count| *count = 0);
+        arena.next_side_index.iter_mut().for_each(|index| *index = 0);
+        arena.seeded.iter_mut().for_each(|seed| *seed = 0);
+        arena.sqrt_pools.iter_mut().for_each(|weight| *weight = 0);
+        arena.settled_sqrt_pool = 0;
+        arena.distribution_cursor = 0;
+        arena.fully_distributed = false;
+        arena.settled_at = 0;
+        arena.accrued_fees = 0;
+        arena.settled_winner_pool = 0;
+        arena.settled_net_loser_pool = 0;
+        arena.settled_loser_pool = 0;
+        arena.settled_fee_bps = 0;
+        // Paid out to the last round's winners; the vault is empty
+        arena.house_bonus = 0;
+        // Last round's stakes keep their old seeds and can no longer be read as this round's
+        arena.epoch = arena.epoch.checked_add(1).ok_or(SoliseumError::MathOverflow)?;
+        arena.claim_deadline = 0;
         arena.settlement_nonce = arena.settlement_nonce.checked_add(1).ok_or(SoliseumError::MathOverflow)?;
+        arena.reset_count = arena.reset_count.checked_add(1).ok_or(SoliseumError::MathOverflow)?;
+
+        emit!(ArenaReset {
+            arena: ctx.accounts.arena.key(),
+            settlement_nonce: ctx.accounts.arena.settlement_nonce,
+            fee_bps,
+            reset_count: ctx.accounts.arena.reset_count,
+            epoch: ctx.accounts.arena.epoch,
+        });
 
         Ok(())
     }
 
-    /// Settle the game with the winner. Requires 2-of-3 oracle signatures.
-    pub fn settle_game(
-        ctx: Context<SettleGame>,
+    /// Settle the game with the winner. Requires a threshold of oracle signatures.
+    /// A one-sided book (stake on fewer than two outcomes) is no contest: the signed result
+    /// voids the arena instead, cancelling it so every staker is refunded via `refund_stake`.
+    /// Optional `remaining_accounts`: `OracleStats` PDAs of signing oracles, each credited
+    /// with one signed settlement.
+    /// `outcome_hash` commits the oracles to off-chain match evidence (e.g. a hash of the
+    /// game log); it is signed along with the winner and kept as `result_hash`. `None`
+    /// signs and stores the legacy message with a zero hash.
+    /// `expected_nonce` is the nonce the oracles signed at; a mismatch (e.g. after a reset
+    /// raced the signing round) fails with `StaleNonce` before any signature is checked.
+    pub fn settle_game<'info>(
+        ctx: Context<'_, '_, '_, 'info, SettleGame<'info>>,
         winner: u8,
         oracle_signatures: Vec<OracleSignature>,
+        outcome_hash: Option<[u8; 32]>,
+        expected_nonce: u64,
     ) -> Result<()> {
-        require!(winner <= 1, SoliseumError::InvalidArenaState);
-        require!(
-            ctx.accounts.arena.status == ArenaStatus::Active,
-            SoliseumError::InvalidArenaState
-        );
         require!(
-            oracle_signatures.len() >= ctx.accounts.arena.oracle_threshold as usize,
-            SoliseumError::InsufficientSignatures
+            expected_nonce == ctx.accounts.arena.settlement_nonce,
+            SoliseumError::StaleNonce
         );
+        let result_hash = outcome_hash.unwrap_or_default();
+        let now = Clock::get()?.unix_timestamp;
+        let winner = check_settleable(&ctx.accounts.arena, winner, now)?;
 
-        let arena = &ctx.accounts.arena;
         let arena_key = ctx.accounts.arena.key();
-        let settlement_nonce = arena.settlement_nonce;
-        
-        // Verify all signatures are from different oracles
-        let mut used_indices = Vec::new();
-        for sig in &oracle_signatures {
-            require!(
-                !used_indices.contains(&sig.oracle_index),
-                SoliseumError::DuplicateOracle
-            );
-            require!(
-                sig.oracle_index < MAX_ORACLES as u8,
-                SoliseumError::InvalidOracleIndex
-            );
-            used_indices.push(sig.oracle_index);
-            
-            // Verify signature over arena address + winner + nonce (prevents replay attacks)
-            let message = create_settlement_message(&arena_key, winner, settlement_nonce);
-            require!(
-                verify_ed25519_signature(
-                    &arena.oracles[sig.oracle_index as usize],
-                    &message,
-                    &sig.signature
-                ),
-                SoliseumError::InvalidSignature
-            );
-        }
+        // CreatorOnly: the submitter constraint already pinned the creator, and no
+        // committee member stands behind the result
+        let signers = match ctx.accounts.arena.settlement_mode {
+            SettlementMode::CreatorOnly => 0,
+            SettlementMode::OracleCommittee => {
+                let signers = verify_settlement(
+                    &ctx.accounts.arena,
+                    ctx.program_id,
+                    &arena_key,
+                    winner,
+                    &result_hash,
+                    &oracle_signatures,
+                )?;
+                require!(
+                    ctx.accounts.arena.signer_groups(signers) >= ctx.accounts.arena.min_signer_groups,
+                    SoliseumError::InsufficientSignerDiversity
+                );
+                signers
+            }
+        };
 
         let arena = &mut ctx.accounts.arena;
-        arena.winner = Some(winner);
-        arena.status = ArenaStatus::Settled;
-        arena.settlement_nonce = arena.settlement_nonce.checked_add(1).ok_or(SoliseumError::MathOverflow)?;
+        if apply_settlement(arena, arena_key, winner, signers, result_hash, now)? {
+            credit_oracle_stats(arena, signers, ctx.remaining_accounts, ctx.program_id, false)?;
+        }
 
         Ok(())
     }
 
-    /// Update oracle committee. Requires 2-of-3 current oracle signatures OR creator.
-    pub fn update_oracles(
-        ctx: Context<UpdateOracles>,
-        new_oracles: [Pubkey; MAX_ORACLES],
-        oracle_signatures: Option<Vec<OracleSignature>>,
+    /// Settle several arenas from one oracle transaction. `remaining_accounts` starts with up
+    /// to MAX_SETTLE_BATCH arenas and `settlements[i]` is the result for the i-th, verified
+    /// against that arena's own committee and nonce exactly as in `settle_game`. Any
+    /// accounts after the arenas are `OracleStats` PDAs, as in `settle_game`; each settled
+    /// arena credits those of its own signers once.
+    /// An arena that cannot settle yet (stale nonce, not Active, betting open, locked out,
+    /// too few participants or signer groups) is skipped with `SettlementSkipped`; an invalid
+    /// signature fails the whole batch.
+    pub fn settle_games_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, SettleGamesBatch<'info>>,
+        settlements: Vec<BatchSettlement>,
     ) -> Result<()> {
+        let remaining = ctx.remaining_accounts;
         require!(
-            new_oracles.iter().all(|pk| *pk != Pubkey::default()),
-            SoliseumError::InvalidOracleConfig
+            !settlements.is_empty()
+                && settlements.len() <= MAX_SETTLE_BATCH
+                && remaining.len() >= settlements.len(),
+            SoliseumError::InvalidSettleBatch
         );
-        
-        // Ensure all new oracles are unique
-        for i in 0..MAX_ORACLES {
-            for j in (i + 1)..MAX_ORACLES {
-                require!(
-                    new_oracles[i] != new_oracles[j],
-                    SoliseumError::InvalidOracleConfig
-                );
+        let (arenas, stats) = remaining.split_at(settlements.len());
+        let oracle = ctx.accounts.oracle.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        for (arena_info, entry) in arenas.iter().zip(settlements.iter()) {
+            let mut arena = Account::<Arena>::try_from(arena_info)?;
+            require!(arena.version == ARENA_VERSION, SoliseumError::UnsupportedVersion);
+            require!(arena.may_submit_settlement(&oracle), SoliseumError::UnauthorizedOracle);
+            let arena_key = arena.key();
+
+            let checked = if entry.expected_nonce == arena.settlement_nonce {
+                check_settleable(&arena, entry.winner, now)
+            } else {
+                Err(error!(SoliseumError::StaleNonce))
+            };
+            let winner = match checked {
+                Ok(winner) => winner,
+                Err(err) => {
+                    emit!(SettlementSkipped {
+                        arena: arena_key,
+                        error_code: error_code_number(&err),
+                    });
+                    continue;
+                }
+            };
+
+            let result_hash = entry.outcome_hash.unwrap_or_default();
+            let signers = match arena.settlement_mode {
+                SettlementMode::CreatorOnly => 0,
+                SettlementMode::OracleCommittee => verify_settlement(
+                    &arena,
+                    ctx.program_id,
+                    &arena_key,
+                    winner,
+                    &result_hash,
+                    &entry.oracle_signatures,
+                )?,
+            };
+            if arena.settlement_mode == SettlementMode::OracleCommittee
+                && arena.signer_groups(signers) < arena.min_signer_groups
+            {
+                emit!(SettlementSkipped {
+                    arena: arena_key,
+                    error_code: error_code_number(&error!(SoliseumError::InsufficientSignerDiversity)),
+                });
+                continue;
             }
-        }
 
-        let arena = &ctx.accounts.arena;
-        let is_creator = ctx.accounts.authority.key() == arena.creator;
-        
-        if !is_creator {
-            let sigs = oracle_signatures.ok_or(SoliseumError::UnauthorizedOracle)?;
-            require!(
-                sigs.len() >= arena.oracle_threshold as usize,
-                SoliseumError::InsufficientSignatures
-            );
-            
-            let mut used_indices = Vec::new();
-            for sig in &sigs {
-                require!(
-                    !used_indices.contains(&sig.oracle_index),
-                    SoliseumError::DuplicateOracle
-                );
-                require!(
-                    sig.oracle_index < MAX_ORACLES as u8,
-                    SoliseumError::InvalidOracleIndex
-                );
-                used_indices.push(sig.oracle_index);
-                
-                let message = create_oracle_update_message(
-                    &ctx.accounts.arena.key(),
-                    &new_oracles,
-                    arena.settlement_nonce
-                );
-                require!(
-                    verify_ed25519_signature(
-                        &arena.oracles[sig.oracle_index as usize],
-                        &message,
-                        &sig.signature
-                    ),
-                    SoliseumError::InvalidSignature
-                );
+            if apply_settlement(&mut arena, arena_key, winner, signers, result_hash, now)? {
+                credit_batch_oracle_stats(&arena, signers, stats, ctx.program_id)?;
             }
+            arena.exit(ctx.program_id)?;
         }
 
-        let arena = &mut ctx.accounts.arena;
-        arena.oracles = new_oracles;
-        arena.settlement_nonce = arena.settlement_nonce.checked_add(1).ok_or(SoliseumError::MathOverflow)?;
-
         Ok(())
     }
 
-    /// Claim reward for winners. Reentrancy protection: claimed = true before transfer.
-    pub fn claim_reward(ctx: Context<ClaimReward>) -> Result<()> {
-        let arena = &ctx.accounts.arena;
-        let stake = &mut ctx.accounts.stake;
-
-        require!(!stake.claimed, SoliseumError::AlreadyClaimed);
+    /// Settle the game as a draw / no-contest. Requires a threshold of oracle signatures
+    /// over a draw message distinct from any winner settlement. Every stake on either side
+    /// can then be refunded its `stake.amount` through the claim instructions, with no fee.
+    pub fn settle_draw(
+        ctx: Context<SettleDraw>,
+        oracle_signatures: Vec<OracleSignature>,
+    ) -> Result<()> {
+        ctx.accounts.arena.require_status(ArenaStatus::Active)?;
+        let closes_at = ctx.accounts.arena.betting_closes_at;
         require!(
-            arena.status == ArenaStatus::Settled,
-            SoliseumError::InvalidArenaState
+            closes_at == 0 || Clock::get()?.unix_timestamp >= closes_at,
+            SoliseumError::BettingStillOpen
         );
 
-        let winner = arena.winner.ok_or(SoliseumError::InvalidArenaState)?;
-        require!(stake.side == winner, SoliseumError::InvalidArenaState);
-
-        let total_winner_pool = if winner == 0 {
-            arena.agent_a_pool
-        } else {
-            arena.agent_b_pool
-        };
-        let total_loser_pool = if winner == 0 {
-            arena.agent_b_pool
-        } else {
-            arena.agent_a_pool
-        };
+        let arena = &ctx.accounts.arena;
+        let arena_key = ctx.accounts.arena.key();
+        let settlement_nonce = arena.settlement_nonce;
 
-        require!(total_winner_pool > 0, SoliseumError::MathOverflow);
+        let message = create_draw_message(ctx.program_id, arena.cluster_id, &arena_key, settlement_nonce);
+        let signers = verify_oracle_signatures(arena, &message, &oracle_signatures)?;
 
-        let fee_bps = arena.fee_bps as u64;
-        let net_loser_pool = (total_loser_pool as u128)
-            .checked_mul(BPS_DENOMINATOR.saturating_sub(fee_bps) as u128)
-            .ok_or(SoliseumError::MathOverflow)?
-            .checked_div(BPS_DENOMINATOR as u128)
-            .ok_or(SoliseumError::MathOverflow)?;
+        let arena = &mut ctx.accounts.arena;
+        arena.winner = Some(DRAW);
+        arena.result_hash = [0; 32];
+        arena.status = arena.settled_status();
+        arena.cache_settlement()?;
+        arena.unclaimed_count = arena.owed_stakers();
+        arena.settled_at = Clock::get()?.unix_timestamp;
+        arena.set_claim_deadline()?;
+        arena.record_signers(signers);
+        arena.settlement_nonce = arena.settlement_nonce.checked_add(1).ok_or(SoliseumError::MathOverflow)?;
 
-        let user_reward = (stake.amount as u128)
-            .checked_mul(net_loser_pool)
-            .ok_or(SoliseumError::MathOverflow)?
-            .checked_div(total_winner_pool as u128)
-            .ok_or(SoliseumError::MathOverflow)?;
+        emit!(GameSettled {
+            arena: arena_key,
+            winner: DRAW,
+            nonce: settlement_nonce,
+            signers,
+            result_hash: [0; 32],
+        });
 
-        let total_payout = (stake.amount as u128)
-            .checked_add(user_reward)
-            .ok_or(SoliseumError::MathOverflow)?;
-        let total_payout_u64: u64 = total_payout.try_into().map_err(|_| SoliseumError::MathOverflow)?;
+        Ok(())
+    }
 
-        stake.claimed = true;
+    /// Correct a mis-settled winner within the dispute window. Requires a threshold of
+    /// oracle signatures over the correction message; the arena stays Settled so staking
+    /// does not reopen.
+    ///
+    /// Lamports already paid out cannot be clawed back on-chain, so a correction is
+    /// rejected with `ClaimsAlreadyPaid` once any stake on the wrong side has claimed.
+    /// Optional `remaining_accounts`: `OracleStats` PDAs of the oracles that signed the
+    /// overturned result, each charged with a lost dispute.
+    pub fn correct_winner<'info>(
+        ctx: Context<'_, '_, '_, 'info, CorrectWinner<'info>>,
+        new_winner: u8,
+        oracle_signatures: Vec<OracleSignature>,
+    ) -> Result<()> {
+        let arena = &ctx.accounts.arena;
+        let old_winner = arena.winner.ok_or(SoliseumError::InvalidArenaState)?;
+        require!(
+            Outcome::from_side(old_winner, arena.outcomes).is_some()
+                && Outcome::from_side(new_winner, arena.outcomes).is_some()
+                && new_winner != old_winner,
+            SoliseumError::InvalidArenaState
+        );
+        require!(
+            arena.in_dispute_window(Clock::get()?.unix_timestamp),
+            SoliseumError::DisputeWindowClosed
+        );
+        require!(
+            arena.unclaimed_count == arena.owed_stakers(),
+            SoliseumError::ClaimsAlreadyPaid
+        );
 
-        let (_, vault_bump) = Pubkey::find_program_address(
-            &[b"vault", arena.creator.as_ref()],
+        let arena_key = ctx.accounts.arena.key();
+        let message = create_correction_message(
             ctx.program_id,
+            arena.cluster_id,
+            &arena_key,
+            old_winner,
+            new_winner,
+            arena.settlement_nonce,
         );
-        let vault_seeds = &[
-            b"vault",
-            arena.creator.as_ref(),
-            &[vault_bump],
-        ];
-        let vault_signer = &[&vault_seeds[..]];
+        let signers = verify_oracle_signatures(arena, &message, &oracle_signatures)?;
 
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.vault.to_account_info(),
-            to: ctx.accounts.user.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            cpi_accounts,
-            vault_signer,
-        );
-        transfer(cpi_ctx, total_payout_u64)?;
+        let arena = &mut ctx.accounts.arena;
+        credit_oracle_stats(arena, arena.settlement_signers, ctx.remaining_accounts, ctx.program_id, true)?;
+        arena.winner = Some(new_winner);
+        arena.result_hash = [0; 32];
+        arena.record_signers(signers);
+        arena.cache_settlement()?;
+        arena.unclaimed_count = arena.owed_stakers();
+        arena.distribution_cursor = 0;
+        arena.fully_distributed = false;
+        arena.settlement_nonce = arena.settlement_nonce.checked_add(1).ok_or(SoliseumError::MathOverflow)?;
 
         Ok(())
     }
-}
-
-// Helper functions (outside #[program] block)
 
-fn create_settlement_message(arena: &Pubkey, winner: u8, nonce: u64) -> Vec<u8> {
-    let mut msg = Vec::with_capacity(41);
-    msg.extend_from_slice(b"soliseum:settle:");
-    msg.extend_from_slice(&arena.to_bytes());
-    msg.push(winner);
-    msg.extend_from_slice(&nonce.to_le_bytes());
-    msg
-}
+    /// Permissionless: opens claims on a `Disputable` arena once its challenge window has
+    /// passed without a dispute.
+    pub fn finalize_settlement(ctx: Context<FinalizeSettlement>) -> Result<()> {
+        let arena = &mut ctx.accounts.arena;
+        require!(
+            !arena.in_challenge_window(Clock::get()?.unix_timestamp),
+            SoliseumError::ChallengeWindowOpen
+        );
+        arena.status = ArenaStatus::Settled;
 
-fn create_reset_message(arena: &Pubkey, nonce: u64) -> Vec<u8> {
-    let mut msg = Vec::with_capacity(40);
-    msg.extend_from_slice(b"soliseum:reset:");
-    msg.extend_from_slice(&arena.to_bytes());
-    msg.extend_from_slice(&nonce.to_le_bytes());
-    msg
-}
+        emit!(SettlementFinalized {
+            arena: arena.key(),
+            winner: arena.winner.ok_or(SoliseumError::InvalidArenaState)?,
+        });
 
-fn create_oracle_update_message(arena: &Pubkey, new_oracles: &[Pubkey; 3], nonce: u64) -> Vec<u8> {
-    let mut msg = Vec::with_capacity(128);
-    msg.extend_from_slice(b"soliseum:update_oracles:");
-    msg.extend_from_slice(&arena.to_bytes());
-    for oracle in new_oracles.iter() {
-        msg.extend_from_slice(&oracle.to_bytes());
+        Ok(())
     }
-    msg.extend_from_slice(&nonce.to_le_bytes());
-    msg
-}
 
-/// Verifies Ed25519 signatures using the Solana native Ed25519 program.
-/// 
+    /// Contest a `Disputable` settlement inside its challenge window. Requires the creator
+    /// OR a threshold of oracle signatures over a dispute message, submitted by a committee
+    /// member or listed relayer. The arena returns to
+    /// `Active` and the nonce moves on, so the disputed result's signatures are void.
+    pub fn dispute_settlement(
+        ctx: Context<DisputeSettlement>,
+        oracle_signatures: Option<Vec<OracleSignature>>,
+    ) -> Result<()> {
+        let arena = &ctx.accounts.arena;
+        require!(
+            arena.in_challenge_window(Clock::get()?.unix_timestamp),
+            SoliseumError::ChallengeWindowClosed
+        );
+
+        if ctx.accounts.authority.key() != arena.creator {
+            require!(
+                arena.may_relay(&ctx.accounts.authority.key()),
+                SoliseumError::UnauthorizedOracle
+            );
+            let sigs = oracle_signatures.ok_or(SoliseumError::UnauthorizedOracle)?;
+            let message = create_dispute_message(
+                ctx.program_id,
+                arena.cluster_id,
+                &ctx.accounts.arena.key(),
+                arena.settlement_nonce,
+            );
+            verify_oracle_signatures(arena, &message, &sigs)?;
+        }
+
+        let arena = &mut ctx.accounts.arena;
+        let disputed_winner = arena.winner.ok_or(SoliseumError::InvalidArenaState)?;
+        arena.status = ArenaStatus::Active;
+        arena.winner = None;
+        arena.result_hash = [0; 32];
+        arena.settled_winner_pool = 0;
+        arena.settled_net_loser_pool = 0;
+        arena.settled_loser_pool = 0;
+        arena.settled_fee_bps = 0;
+        arena.unclaimed_count = 0;
+        arena.settled_at = 0;
+        arena.claim_deadline = 0;
+        arena.settlement_nonce = arena.settlement_nonce.checked_add(1).ok_or(SoliseumError::MathOverflow)?;
+
+        emit!(SettlementDisputed {
+            arena: arena.key(),
+            disputed_winner,
+            settlement_nonce: arena.settlement_nonce,
+        });
+
+        Ok(())
+    }
+
+    /// Creates the program-wide `GlobalConfig` aggregates. The PDA is a singleton, so this
+    /// succeeds once; counters only move when the account is passed to the instructions
+    /// that feed them.
+    pub fn init_global_config(ctx: Context<InitGlobalConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.global_config;
+        config.total_arenas = 0;
+        config.total_volume_lamports = 0;
+        config.total_fees_collected = 0;
+        Ok(())
+    }
+
+    /// Creates the reliability counters for `oracle`. Anyone may pay for them; they are only
+    /// updated when passed to `settle_game` or `correct_winner`.
+    pub fn init_oracle_stats(ctx: Context<InitOracleStats>, oracle: Pubkey) -> Result<()> {
+        let stats = &mut ctx.accounts.oracle_stats;
+        stats.oracle = oracle;
+        stats.settlements_signed = 0;
+        stats.disputes_lost = 0;
+        Ok(())
+    }
+
+    /// Propose a new oracle committee. Requires the creator, OR a threshold of current oracle
+    /// signatures submitted by a current member. The committee keeps its size; members are replaced in place by
+    /// `execute_oracle_update` once `oracle_update_delay_secs` have passed.
+    pub fn propose_oracle_update(
+        ctx: Context<ProposeOracleUpdate>,
+        new_oracles: Vec<Pubkey>,
+        oracle_signatures: Option<Vec<OracleSignature>>,
+    ) -> Result<()> {
+        // The account was sized for the original committee, so members rotate in place
+        require!(
+            new_oracles.len() == ctx.accounts.arena.oracles.len(),
+            SoliseumError::InvalidOracleConfig
+        );
+        validate_committee(&new_oracles)?;
+        require!(
+            ctx.accounts.arena.pending_oracles.is_empty(),
+            SoliseumError::OracleUpdatePending
+        );
+
+        let arena = &ctx.accounts.arena;
+        let is_creator = ctx.accounts.authority.key() == arena.creator;
+        
+        if !is_creator {
+            // Relayers only carry results; the committee's makeup is for its members
+            require!(
+                arena.oracles.contains(&ctx.accounts.authority.key()),
+                SoliseumError::UnauthorizedOracle
+            );
+            let sigs = oracle_signatures.ok_or(SoliseumError::UnauthorizedOracle)?;
+            let message = create_oracle_update_message(
+                ctx.program_id,
+                arena.cluster_id,
+                &ctx.accounts.arena.key(),
+                &new_oracles,
+                arena.settlement_nonce
+            );
+            verify_oracle_signatures(arena, &message, &sigs)?;
+        }
+
+        let arena = &mut ctx.accounts.arena;
+        arena.oracle_update_effective_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(arena.oracle_update_delay_secs)
+            .ok_or(SoliseumError::MathOverflow)?;
+        arena.pending_oracles = new_oracles;
+
+        emit!(OracleUpdateProposed {
+            arena: ctx.accounts.arena.key(),
+            pending_oracles: ctx.accounts.arena.pending_oracles.clone(),
+            effective_at: ctx.accounts.arena.oracle_update_effective_at,
+        });
+
+        Ok(())
+    }
+
+    /// Apply a proposed committee once its timelock has passed. Permissionless: the
+    /// proposal was already authorized by `propose_oracle_update`.
+    pub fn execute_oracle_update(ctx: Context<ExecuteOracleUpdate>) -> Result<()> {
+        let arena = &mut ctx.accounts.arena;
+        require!(!arena.pending_oracles.is_empty(), SoliseumError::NoPendingOracleUpdate);
+        require!(
+            Clock::get()?.unix_timestamp >= arena.oracle_update_effective_at,
+            SoliseumError::OracleUpdateTimelocked
+        );
+
+        arena.oracles = std::mem::take(&mut arena.pending_oracles);
+        arena.oracle_update_effective_at = 0;
+        arena.settlement_nonce = arena.settlement_nonce.checked_add(1).ok_or(SoliseumError::MathOverflow)?;
+
+        emit!(OraclesUpdated {
+            arena: ctx.accounts.arena.key(),
+            oracles: ctx.accounts.arena.oracles.clone(),
+            settlement_nonce: ctx.accounts.arena.settlement_nonce,
+        });
+
+        Ok(())
+    }
+
+    /// Creator veto for a pending committee proposal, e.g. one pushed through with
+    /// compromised oracle keys. Bumps the nonce so the proposal's signatures cannot be
+    /// replayed.
+    pub fn cancel_oracle_update(ctx: Context<CancelOracleUpdate>) -> Result<()> {
+        let arena = &mut ctx.accounts.arena;
+        require!(!arena.pending_oracles.is_empty(), SoliseumError::NoPendingOracleUpdate);
+
+        arena.pending_oracles = Vec::new();
+        arena.oracle_update_effective_at = 0;
+        arena.settlement_nonce = arena.settlement_nonce.checked_add(1).ok_or(SoliseumError::MathOverflow)?;
+
+        emit!(OracleUpdateCancelled {
+            arena: ctx.accounts.arena.key(),
+            settlement_nonce: ctx.accounts.arena.settlement_nonce,
+        });
+
+        Ok(())
+    }
+
+    /// Let the oracle holding seat `index` hand it to `new_pubkey`, leaving every other seat
+    /// as is. The current holder signs the transaction and the seat rotation message, so no
+    /// committee vote or timelock is needed; the nonce bump voids outstanding signatures.
+    pub fn rotate_oracle_seat(
+        ctx: Context<RotateOracleSeat>,
+        index: u8,
+        new_pubkey: Pubkey,
+        signature: [u8; 64],
+    ) -> Result<()> {
+        let arena = &ctx.accounts.arena;
+        let old_pubkey = *arena
+            .oracles
+            .get(index as usize)
+            .ok_or(SoliseumError::InvalidOracleConfig)?;
+        require_keys_eq!(ctx.accounts.oracle.key(), old_pubkey, SoliseumError::UnauthorizedOracle);
+        require!(
+            new_pubkey != Pubkey::default() && !arena.oracles.contains(&new_pubkey),
+            SoliseumError::InvalidOracleConfig
+        );
+        // A pending proposal was voted on against the current seats
+        require!(arena.pending_oracles.is_empty(), SoliseumError::OracleUpdatePending);
+
+        let arena_key = ctx.accounts.arena.key();
+        let message = create_seat_rotation_message(
+            ctx.program_id,
+            arena.cluster_id,
+            &arena_key,
+            index,
+            &old_pubkey,
+            &new_pubkey,
+            arena.settlement_nonce,
+        );
+        require!(
+            verify_ed25519_signature(&old_pubkey, &message, &signature),
+            SoliseumError::InvalidSignature
+        );
+
+        let arena = &mut ctx.accounts.arena;
+        arena.oracles[index as usize] = new_pubkey;
+        arena.settlement_nonce = arena.settlement_nonce.checked_add(1).ok_or(SoliseumError::MathOverflow)?;
+
+        emit!(OracleSeatRotated {
+            arena: arena_key,
+            index,
+            old_oracle: old_pubkey,
+            new_oracle: new_pubkey,
+            settlement_nonce: arena.settlement_nonce,
+        });
+
+        Ok(())
+    }
+
+    /// Abort an Active arena whose battle never happened so every staker can take their
+    /// stake back via `refund_stake`. Requires the creator's signature, OR a threshold of
+    /// oracle signatures submitted by a committee member or listed relayer.
+    pub fn cancel_arena(
+        ctx: Context<CancelArena>,
+        oracle_signatures: Option<Vec<OracleSignature>>,
+    ) -> Result<()> {
+        let arena = &ctx.accounts.arena;
+        let is_creator = ctx.accounts.authority.key() == arena.creator;
+
+        if !is_creator {
+            require!(
+                arena.may_relay(&ctx.accounts.authority.key()),
+                SoliseumError::UnauthorizedOracle
+            );
+            let sigs = oracle_signatures.ok_or(SoliseumError::UnauthorizedOracle)?;
+            let message = create_cancel_message(
+                ctx.program_id,
+                arena.cluster_id,
+                &ctx.accounts.arena.key(),
+                arena.settlement_nonce,
+            );
+            verify_oracle_signatures(arena, &message, &sigs)?;
+        }
+
+        let arena = &mut ctx.accounts.arena;
+        arena.status = ArenaStatus::Cancelled;
+        arena.unclaimed_count = arena.total_stakers()?;
+        arena.settlement_nonce = arena.settlement_nonce.checked_add(1).ok_or(SoliseumError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Fallback for an arena whose oracles went dark: once `emergency_timeout_secs` have
+    /// passed since creation (or since betting closed, so the oracles always get their full
+    /// settlement window), the creator can cancel a still-Active arena without oracle
+    /// signatures and stakers recover their stakes via `refund_stake`.
+    pub fn emergency_cancel(ctx: Context<EmergencyCancel>) -> Result<()> {
+        let arena = &mut ctx.accounts.arena;
+        require!(
+            Clock::get()?.unix_timestamp >= arena.emergency_cancel_at()?,
+            SoliseumError::EmergencyTimeoutPending
+        );
+
+        arena.status = ArenaStatus::Cancelled;
+        arena.unclaimed_count = arena.total_stakers()?;
+        arena.settlement_nonce = arena.settlement_nonce.checked_add(1).ok_or(SoliseumError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Claim reward for winners. Reentrancy protection: claimed = true before transfer.
+    /// The last stake to claim also receives the rounding dust (see `record_claim`).
+    /// With `recipient` set the payout goes to that account, passed as the first
+    /// `remaining_accounts` entry, while the stake owner still signs; None pays the signer.
+    pub fn claim_reward<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimReward<'info>>,
+        recipient: Option<Pubkey>,
+    ) -> Result<()> {
+        let payee = match recipient {
+            Some(key) => {
+                let info = ctx
+                    .remaining_accounts
+                    .first()
+                    .ok_or(SoliseumError::InvalidRecipient)?;
+                require_keys_eq!(info.key(), key, SoliseumError::InvalidRecipient);
+                // Routing the payout back into program accounts or to an executable
+                // would burn it; only plain writable wallets may receive it
+                require!(
+                    info.is_writable
+                        && !info.executable
+                        && key != ctx.accounts.vault.key()
+                        && key != ctx.accounts.arena.key()
+                        && key != ctx.accounts.stake.key(),
+                    SoliseumError::InvalidRecipient
+                );
+                info.clone()
+            }
+            None => ctx.accounts.user.to_account_info(),
+        };
+
+        let stake_info = ctx.accounts.stake.to_account_info();
+        let mut stake = load_stake(
+            &stake_info,
+            ctx.program_id,
+            &ctx.accounts.arena.key(),
+            ctx.accounts.arena.epoch,
+            &ctx.accounts.user.key(),
+        )?;
+        let vault_lamports = ctx.accounts.vault.lamports();
+        let arena = &mut ctx.accounts.arena;
+
+        require!(!stake.claimed, SoliseumError::AlreadyClaimed);
+        arena.require_status(ArenaStatus::Settled)?;
+
+        // Refunds return the staked SOL even on arenas that pay winners in tokens
+        require!(
+            arena.payout_mint.is_none() || arena.refunds_all(),
+            SoliseumError::WrongPayoutCurrency
+        );
+
+        let total_payout_u64 = record_claim(arena, &mut stake, vault_lamports)?;
+        store_stake(&stake_info, &stake)?;
+
+        pay_from_vault(
+            &ctx.accounts.arena,
+            &ctx.accounts.vault.to_account_info(),
+            &payee,
+            &ctx.accounts.system_program,
+            total_payout_u64,
+        )?;
+
+        emit!(RewardClaimed {
+            arena: ctx.accounts.arena.key(),
+            user: ctx.accounts.user.key(),
+            payout: total_payout_u64,
+        });
+
+        Ok(())
+    }
+
+    /// Claim `amount` lamports of a settled SOL payout, for treasuries that pull winnings
+    /// in chunks. Each call pays out of what remains after earlier chunks; the stake is
+    /// marked claimed (and the fee booked) by the chunk that completes the payout, which
+    /// also collects the last claimant's dust. `claim_reward` pays whatever remains.
+    /// Only available once the dispute window has closed, so a correction never has to
+    /// unwind a half-paid stake.
+    pub fn claim_reward_partial(ctx: Context<ClaimReward>, amount: u64) -> Result<()> {
+        let stake_info = ctx.accounts.stake.to_account_info();
+        let mut stake = load_stake(
+            &stake_info,
+            ctx.program_id,
+            &ctx.accounts.arena.key(),
+            ctx.accounts.arena.epoch,
+            &ctx.accounts.user.key(),
+        )?;
+        let vault_lamports = ctx.accounts.vault.lamports();
+        let arena = &mut ctx.accounts.arena;
+
+        require!(!stake.claimed, SoliseumError::AlreadyClaimed);
+        require!(amount > 0, SoliseumError::MathOverflow);
+        require!(
+            !arena.in_dispute_window(Clock::get()?.unix_timestamp),
+            SoliseumError::DisputeWindowOpen
+        );
+        require!(
+            arena.payout_mint.is_none() || arena.refunds_all(),
+            SoliseumError::WrongPayoutCurrency
+        );
+
+        let payout = record_partial_claim(arena, &mut stake, vault_lamports, amount)?;
+        store_stake(&stake_info, &stake)?;
+
+        let arena_key = arena.key();
+        pay_from_vault(
+            &ctx.accounts.arena,
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program,
+            payout,
+        )?;
+        emit!(RewardClaimed {
+            arena: arena_key,
+            user: ctx.accounts.user.key(),
+            payout,
+        });
+
+        Ok(())
+    }
+
+    /// Non-failing `claim_reward`: returns a `ClaimStatus` through return data rather than
+    /// erroring when the stake was already claimed, the arena is not settled or the stake
+    /// lost, leaving state untouched in those cases. A winning (or refunded) stake is paid
+    /// exactly as `claim_reward` would pay it.
+    pub fn try_claim(ctx: Context<TryClaim>) -> Result<ClaimStatus> {
+        let stake_info = ctx.accounts.stake.to_account_info();
+        let mut stake = load_stake(
+            &stake_info,
+            ctx.program_id,
+            &ctx.accounts.arena.key(),
+            ctx.accounts.arena.epoch,
+            &ctx.accounts.user.key(),
+        )?;
+        let vault_lamports = ctx.accounts.vault.lamports();
+        let arena = &mut ctx.accounts.arena;
+
+        if stake.claimed {
+            return Ok(ClaimStatus::AlreadyClaimed);
+        }
+        if arena.status != ArenaStatus::Settled {
+            return Ok(ClaimStatus::NotSettled);
+        }
+        if !arena.refunds_all() && settled_outcome(arena, &stake)? != Outcome::Side(stake.side) {
+            return Ok(ClaimStatus::NotAWinner);
+        }
+        require!(
+            arena.payout_mint.is_none() || arena.refunds_all(),
+            SoliseumError::WrongPayoutCurrency
+        );
+
+        let payout = record_claim(arena, &mut stake, vault_lamports)?;
+        store_stake(&stake_info, &stake)?;
+
+        let arena_key = arena.key();
+        pay_from_vault(
+            &ctx.accounts.arena,
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program,
+            payout,
+        )?;
+        emit!(RewardClaimed {
+            arena: arena_key,
+            user: ctx.accounts.user.key(),
+            payout,
+        });
+
+        Ok(ClaimStatus::Claimed(payout))
+    }
+
+    /// Permissionless crank: pays a winning stake's claim to its owner on their behalf, so
+    /// operators can push rewards without each user signing. `owner` must be the stake's
+    /// owner (the stake PDA is derived from it) and receives the payout, never the cranker.
+    /// Losing and already-claimed stakes are rejected as in `claim_reward`.
+    pub fn crank_claim(ctx: Context<CrankClaim>) -> Result<()> {
+        let stake_info = ctx.accounts.stake.to_account_info();
+        let owner = ctx.accounts.owner.key();
+        let mut stake = load_stake(
+            &stake_info,
+            ctx.program_id,
+            &ctx.accounts.arena.key(),
+            ctx.accounts.arena.epoch,
+            &owner,
+        )?;
+        let vault_lamports = ctx.accounts.vault.lamports();
+        let arena = &mut ctx.accounts.arena;
+
+        require!(!stake.claimed, SoliseumError::AlreadyClaimed);
+        require!(
+            arena.payout_mint.is_none() || arena.refunds_all(),
+            SoliseumError::WrongPayoutCurrency
+        );
+
+        let payout = record_claim(arena, &mut stake, vault_lamports)?;
+        store_stake(&stake_info, &stake)?;
+
+        let arena_key = arena.key();
+        pay_from_vault(
+            &ctx.accounts.arena,
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program,
+            payout,
+        )?;
+        emit!(RewardClaimed {
+            arena: arena_key,
+            user: owner,
+            payout,
+        });
+
+        Ok(())
+    }
+
+    /// Claim the caller's stakes on several settled SOL arenas in one transaction.
+    /// `remaining_accounts` holds `(arena, vault, stake)` triples, up to MAX_CLAIM_BATCH;
+    /// stakes that were already claimed are skipped rather than failing the batch.
+    pub fn claim_rewards_many<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimRewardsMany<'info>>,
+    ) -> Result<()> {
+        let remaining = ctx.remaining_accounts;
+        require!(
+            !remaining.is_empty()
+                && remaining.len() % 3 == 0
+                && remaining.len() / 3 <= MAX_CLAIM_BATCH,
+            SoliseumError::InvalidClaimBatch
+        );
+        let user = ctx.accounts.user.key();
+
+        for triple in remaining.chunks(3) {
+            let (arena_info, vault_info, stake_info) = (&triple[0], &triple[1], &triple[2]);
+
+            let mut arena = Account::<Arena>::try_from(arena_info)?;
+            require!(arena.version == ARENA_VERSION, SoliseumError::UnsupportedVersion);
+            arena.require_status(ArenaStatus::Settled)?;
+            require!(arena.stake_mint.is_none(), SoliseumError::WrongStakeCurrency);
+            require!(
+                arena.payout_mint.is_none() || arena.refunds_all(),
+                SoliseumError::WrongPayoutCurrency
+            );
+
+            let arena_key = arena.key();
+            let vault_bump = arena.vault_bump;
+            let expected_vault = Pubkey::create_program_address(
+                &[b"vault", arena_key.as_ref(), &[vault_bump]],
+                ctx.program_id,
+            )
+            .map_err(|_| error!(SoliseumError::InvalidArenaState))?;
+            require_keys_eq!(*vault_info.key, expected_vault, SoliseumError::InvalidArenaState);
+            let mut stake = load_stake(stake_info, ctx.program_id, &arena_key, arena.epoch, &user)?;
+            if stake.claimed {
+                continue;
+            }
+
+            let payout = record_claim(&mut arena, &mut stake, vault_info.lamports())?;
+            store_stake(stake_info, &stake)?;
+            arena.exit(ctx.program_id)?;
+
+            pay_from_vault(
+                &arena,
+                vault_info,
+                &ctx.accounts.user.to_account_info(),
+                &ctx.accounts.system_program,
+                payout,
+            )?;
+            emit!(RewardClaimed {
+                arena: arena_key,
+                user,
+                payout,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Claim a winning stake in the arena's payout token. The lamport payout from the
+    /// usual formula is converted at `payout_rate` and paid from the vault's token account,
+    /// which the operator must pre-fund; the staked SOL stays in the vault.
+    pub fn claim_reward_token(ctx: Context<ClaimRewardToken>) -> Result<()> {
+        let stake_info = ctx.accounts.stake.to_account_info();
+        let mut stake = load_stake(
+            &stake_info,
+            ctx.program_id,
+            &ctx.accounts.arena.key(),
+            ctx.accounts.arena.epoch,
+            &ctx.accounts.user.key(),
+        )?;
+        let arena = &mut ctx.accounts.arena;
+
+        require!(!stake.claimed, SoliseumError::AlreadyClaimed);
+        require!(
+            arena.payout_mint == Some(ctx.accounts.payout_mint.key()),
+            SoliseumError::WrongPayoutCurrency
+        );
+
+        let winner = settled_outcome(arena, &stake)?;
+        require!(!arena.refunds_all(), SoliseumError::WrongPayoutCurrency);
+        require!(winner == Outcome::Side(stake.side), SoliseumError::NotAWinner);
+
+        let lamport_payout = winner_payout(arena, stake.amount)?;
+        let token_payout = mul_div(lamport_payout, arena.payout_rate, LAMPORTS_PER_SOL)?;
+        require!(
+            ctx.accounts.payout_vault.amount >= token_payout,
+            SoliseumError::PayoutVaultUnderfunded
+        );
+
+        stake.claimed = true;
+        arena.unclaimed_count = arena.unclaimed_count.checked_sub(1).ok_or(SoliseumError::MathOverflow)?;
+        store_stake(&stake_info, &stake)?;
+
+        pay_tokens_from_vault(
+            &ctx.accounts.arena,
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.payout_vault.to_account_info(),
+            &ctx.accounts.user_token_account.to_account_info(),
+            &ctx.accounts.token_program,
+            token_payout,
+        )?;
+
+        emit!(RewardClaimed {
+            arena: ctx.accounts.arena.key(),
+            user: ctx.accounts.user.key(),
+            payout: token_payout,
+        });
+
+        Ok(())
+    }
+
+    /// Refund a stake on a cancelled arena: returns exactly `stake.amount`, no fee, for
+    /// either side. Pools are drawn down with checked math so refunds can never exceed
+    /// what was staked into the vault.
+    pub fn refund_stake(ctx: Context<RefundStake>) -> Result<()> {
+        let stake_info = ctx.accounts.stake.to_account_info();
+        let mut stake = load_stake(
+            &stake_info,
+            ctx.program_id,
+            &ctx.accounts.arena.key(),
+            ctx.accounts.arena.epoch,
+            &ctx.accounts.user.key(),
+        )?;
+        let arena = &mut ctx.accounts.arena;
+
+        require!(!stake.claimed, SoliseumError::AlreadyClaimed);
+
+        let refund = stake.amount;
+        arena.total_pool = arena.total_pool.checked_sub(refund).ok_or(SoliseumError::MathOverflow)?;
+        let side_pool = &mut arena.pools[stake.side as usize];
+        *side_pool = side_pool.checked_sub(refund).ok_or(SoliseumError::MathOverflow)?;
+        arena.reweigh(stake.side, refund, 0)?;
+
+        stake.claimed = true;
+        arena.unclaimed_count = arena.unclaimed_count.checked_sub(1).ok_or(SoliseumError::MathOverflow)?;
+        store_stake(&stake_info, &stake)?;
+
+        pay_from_vault(
+            &ctx.accounts.arena,
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program,
+            refund,
+        )?;
+
+        Ok(())
+    }
+
+    /// Token-denominated `claim_reward` for arenas with a `stake_mint`. The payout math is
+    /// unchanged but runs on token amounts, paid from the vault's token account.
+    pub fn claim_reward_spl(ctx: Context<ClaimRewardSpl>) -> Result<()> {
+        let stake_info = ctx.accounts.stake.to_account_info();
+        let mut stake = load_stake(
+            &stake_info,
+            ctx.program_id,
+            &ctx.accounts.arena.key(),
+            ctx.accounts.arena.epoch,
+            &ctx.accounts.user.key(),
+        )?;
+        let arena = &mut ctx.accounts.arena;
+
+        require!(!stake.claimed, SoliseumError::AlreadyClaimed);
+
+        let total_payout = settled_payout(arena, &stake)?;
+        require!(
+            ctx.accounts.stake_vault.amount >= total_payout,
+            SoliseumError::PayoutVaultUnderfunded
+        );
+
+        stake.claimed = true;
+        arena.unclaimed_count = arena.unclaimed_count.checked_sub(1).ok_or(SoliseumError::MathOverflow)?;
+        store_stake(&stake_info, &stake)?;
+
+        pay_tokens_from_vault(
+            &ctx.accounts.arena,
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.stake_vault.to_account_info(),
+            &ctx.accounts.user_token_account.to_account_info(),
+            &ctx.accounts.token_program,
+            total_payout,
+        )?;
+
+        emit!(RewardClaimed {
+            arena: ctx.accounts.arena.key(),
+            user: ctx.accounts.user.key(),
+            payout: total_payout,
+        });
+
+        Ok(())
+    }
+
+    /// Token-denominated `refund_stake` for cancelled arenas with a `stake_mint`.
+    pub fn refund_stake_spl(ctx: Context<RefundStakeSpl>) -> Result<()> {
+        let stake_info = ctx.accounts.stake.to_account_info();
+        let mut stake = load_stake(
+            &stake_info,
+            ctx.program_id,
+            &ctx.accounts.arena.key(),
+            ctx.accounts.arena.epoch,
+            &ctx.accounts.user.key(),
+        )?;
+        let arena = &mut ctx.accounts.arena;
+
+        require!(!stake.claimed, SoliseumError::AlreadyClaimed);
+
+        let refund = stake.amount;
+        arena.total_pool = arena.total_pool.checked_sub(refund).ok_or(SoliseumError::MathOverflow)?;
+        let side_pool = &mut arena.pools[stake.side as usize];
+        *side_pool = side_pool.checked_sub(refund).ok_or(SoliseumError::MathOverflow)?;
+        arena.reweigh(stake.side, refund, 0)?;
+
+        stake.claimed = true;
+        arena.unclaimed_count = arena.unclaimed_count.checked_sub(1).ok_or(SoliseumError::MathOverflow)?;
+        store_stake(&stake_info, &stake)?;
+
+        pay_tokens_from_vault(
+            &ctx.accounts.arena,
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.stake_vault.to_account_info(),
+            &ctx.accounts.user_token_account.to_account_info(),
+            &ctx.accounts.token_program,
+            refund,
+        )?;
+
+        Ok(())
+    }
+
+    /// Permissionless push distribution to winners, resumable across transactions.
+    /// `remaining_accounts` holds up to MAX_DISTRIBUTION_CHUNK `(stake, owner)` pairs of
+    /// winning stakes in `side_index` order starting at `distribution_cursor`. Stakes
+    /// already claimed via `claim_reward` are skipped; the arena is flagged
+    /// `fully_distributed` once the cursor passes the last winning stake.
+    pub fn distribute_rewards<'info>(
+        ctx: Context<'_, '_, '_, 'info, DistributeRewards<'info>>,
+    ) -> Result<()> {
+        let remaining = ctx.remaining_accounts;
+        require!(
+            remaining.len() % 2 == 0 && remaining.len() / 2 <= MAX_DISTRIBUTION_CHUNK,
+            SoliseumError::InvalidDistributionBatch
+        );
+        require!(
+            !ctx.accounts.arena.fully_distributed,
+            SoliseumError::InvalidArenaState
+        );
+        require!(
+            !ctx.accounts.arena.claims_expired(Clock::get()?.unix_timestamp),
+            SoliseumError::ClaimExpired
+        );
+        require!(
+            ctx.accounts.arena.payout_mint.is_none(),
+            SoliseumError::WrongPayoutCurrency
+        );
+
+        let arena_key = ctx.accounts.arena.key();
+        let winner = ctx.accounts.arena.winner.ok_or(SoliseumError::InvalidArenaState)?;
+        // The cursor walks a single side's side_index order, so refunds are pull-only
+        require!(!ctx.accounts.arena.refunds_all(), SoliseumError::InvalidArenaState);
+
+        for pair in remaining.chunks(2) {
+            let stake_info = &pair[0];
+            let owner_info = &pair[1];
+
+            let epoch = ctx.accounts.arena.epoch;
+            let mut stake = load_stake(stake_info, ctx.program_id, &arena_key, epoch, owner_info.key)?;
+            require!(stake.side == winner, SoliseumError::NotAWinner);
+            require!(
+                stake.side_index == ctx.accounts.arena.distribution_cursor,
+                SoliseumError::DistributionOutOfOrder
+            );
+
+            let arena = &mut ctx.accounts.arena;
+            arena.distribution_cursor = arena
+                .distribution_cursor
+                .checked_add(1)
+                .ok_or(SoliseumError::MathOverflow)?;
+            if stake.claimed {
+                continue;
+            }
+
+            let payout = winner_payout(arena, stake.amount)?
+                .checked_sub(stake.claimed_amount)
+                .ok_or(SoliseumError::MathOverflow)?;
+            accrue_fee(arena, &mut stake)?;
+            stake.claimed_amount = stake.claimed_amount.checked_add(payout).ok_or(SoliseumError::MathOverflow)?;
+            stake.claimed = true;
+            arena.unclaimed_count = arena.unclaimed_count.checked_sub(1).ok_or(SoliseumError::MathOverflow)?;
+            store_stake(stake_info, &stake)?;
+
+            pay_from_vault(
+                &ctx.accounts.arena,
+                &ctx.accounts.vault.to_account_info(),
+                owner_info,
+                &ctx.accounts.system_program,
+                payout,
+            )?;
+            emit!(RewardClaimed {
+                arena: arena_key,
+                user: *owner_info.key,
+                payout,
+            });
+        }
+
+        let arena = &mut ctx.accounts.arena;
+        if arena.distribution_cursor >= arena.next_side_index[winner as usize] {
+            arena.fully_distributed = true;
+        }
+
+        Ok(())
+    }
+
+    /// Send the protocol fees accrued by SOL claims to `fee_recipient`. Callable by the
+    /// creator or the fee recipient. Once no claim can still be made the rounding dust is
+    /// swept with it, leaving the vault empty so the arena can be reset.
+    /// Optional `remaining_accounts`: the `GlobalConfig` PDA, to count the fees collected.
+    pub fn withdraw_fees<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawFees<'info>>) -> Result<()> {
+        let arena = &ctx.accounts.arena;
+        let authority = ctx.accounts.authority.key();
+        require!(
+            authority == arena.creator || authority == arena.fee_recipient,
+            SoliseumError::InvalidArenaState
+        );
+
+        let settled_out = arena.unclaimed_count == 0
+            && !arena.in_dispute_window(Clock::get()?.unix_timestamp);
+        let amount = if settled_out {
+            // Everything but the referral cuts still owed to referrers, a house bonus owed
+            // back to the creator and the rent reserve
+            arena
+                .spendable(ctx.accounts.vault.lamports())
+                .saturating_sub(arena.referral_owed)
+                .saturating_sub(arena.reclaimable_house_bonus())
+        } else {
+            arena.accrued_fees
+        };
+        require!(
+            amount <= arena.spendable(ctx.accounts.vault.lamports()),
+            SoliseumError::RentReserveBreached
+        );
+
+        let arena = &mut ctx.accounts.arena;
+        arena.fees_withdrawn = arena.fees_withdrawn.checked_add(amount).ok_or(SoliseumError::MathOverflow)?;
+        arena.accrued_fees = 0;
+
+        if amount > 0 {
+            pay_from_vault(
+                &ctx.accounts.arena,
+                &ctx.accounts.vault.to_account_info(),
+                &ctx.accounts.fee_recipient.to_account_info(),
+                &ctx.accounts.system_program,
+                amount,
+            )?;
+        }
+
+        record_global_stats(ctx.remaining_accounts, ctx.program_id, 0, 0, amount)?;
+
+        Ok(())
+    }
+
+    /// Send `amount` of the accrued protocol fees to `fee_recipient`, leaving the rest booked
+    /// in `accrued_fees` for a later withdrawal. Never touches staker principal or dust.
+    pub fn withdraw_partial_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawFees<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let arena = &ctx.accounts.arena;
+        let authority = ctx.accounts.authority.key();
+        require!(
+            authority == arena.creator || authority == arena.fee_recipient,
+            SoliseumError::InvalidArenaState
+        );
+        require!(amount <= arena.accrued_fees, SoliseumError::InsufficientAccruedFees);
+        require!(
+            amount <= arena.spendable(ctx.accounts.vault.lamports()),
+            SoliseumError::RentReserveBreached
+        );
+
+        let arena = &mut ctx.accounts.arena;
+        arena.fees_withdrawn = arena.fees_withdrawn.checked_add(amount).ok_or(SoliseumError::MathOverflow)?;
+        arena.accrued_fees = arena.accrued_fees.checked_sub(amount).ok_or(SoliseumError::MathOverflow)?;
+
+        if amount > 0 {
+            pay_from_vault(
+                &ctx.accounts.arena,
+                &ctx.accounts.vault.to_account_info(),
+                &ctx.accounts.fee_recipient.to_account_info(),
+                &ctx.accounts.system_program,
+                amount,
+            )?;
+        }
+
+        record_global_stats(ctx.remaining_accounts, ctx.program_id, 0, 0, amount)?;
+
+        Ok(())
+    }
+
+    /// Pay a referrer the fee cut set aside when the stake they referred was claimed.
+    pub fn claim_referral(ctx: Context<ClaimReferral>) -> Result<()> {
+        let stake_info = ctx.accounts.stake.to_account_info();
+        let mut stake = load_stake(
+            &stake_info,
+            ctx.program_id,
+            &ctx.accounts.arena.key(),
+            ctx.accounts.arena.epoch,
+            &ctx.accounts.staker.key(),
+        )?;
+        let referrer = ctx.accounts.referrer.key();
+        require!(stake.referrer == Some(referrer), SoliseumError::InvalidReferrer);
+        require!(
+            !ctx.accounts.arena.claims_expired(Clock::get()?.unix_timestamp),
+            SoliseumError::ClaimExpired
+        );
+
+        let amount = stake.referral_owed;
+        require!(amount > 0, SoliseumError::NothingToClaim);
+        stake.referral_owed = 0;
+        store_stake(&stake_info, &stake)?;
+
+        let arena = &mut ctx.accounts.arena;
+        arena.referral_owed = arena.referral_owed.checked_sub(amount).ok_or(SoliseumError::MathOverflow)?;
+
+        pay_from_vault(
+            &ctx.accounts.arena,
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.referrer.to_account_info(),
+            &ctx.accounts.system_program,
+            amount,
+        )?;
+
+        Ok(())
+    }
+
+    /// Close a finished stake and refund its rent to the staker. Allowed once the stake
+    /// has been refunded (Cancelled) or claimed (Settled, winning side), or for a losing
+    /// stake once the dispute window has closed. A claimed winning stake must stay open
+    /// while `distribute_rewards` still has to walk past its `side_index`. Stakes from a
+    /// round before `reset_arena` may always close.
+    pub fn close_stake(ctx: Context<CloseStake>) -> Result<()> {
+        check_stake_closable(&ctx.accounts.arena, &ctx.accounts.stake, Clock::get()?.unix_timestamp)
+    }
+
+    /// Bulk `close_stake`: closes the caller's finished stakes across arenas, returning
+    /// their rent to the caller. `remaining_accounts` holds `(arena, stake)` pairs, up to
+    /// MAX_SWEEP_BATCH; stakes `close_stake` would still refuse are left open and skipped
+    /// rather than failing the sweep. A stake that is not the caller's fails it.
+    pub fn sweep_my_stakes<'info>(ctx: Context<'_, '_, '_, 'info, SweepMyStakes<'info>>) -> Result<()> {
+        let remaining = ctx.remaining_accounts;
+        require!(
+            !remaining.is_empty()
+                && remaining.len() % 2 == 0
+                && remaining.len() / 2 <= MAX_SWEEP_BATCH,
+            SoliseumError::InvalidSweepBatch
+        );
+        let user = ctx.accounts.user.key();
+        let now = Clock::get()?.unix_timestamp;
+        let mut closed = 0u8;
+
+        for pair in remaining.chunks(2) {
+            let (arena_info, stake_info) = (&pair[0], &pair[1]);
+
+            let arena = Account::<Arena>::try_from(arena_info)?;
+            require!(arena.version == ARENA_VERSION, SoliseumError::UnsupportedVersion);
+            let stake = load_stake_any_epoch(stake_info, ctx.program_id, &arena.key(), &user)?;
+            if check_stake_closable(&arena, &stake, now).is_err() {
+                continue;
+            }
+
+            Account::<Stake>::try_from(stake_info)?.close(ctx.accounts.user.to_account_info())?;
+            closed += 1;
+        }
+
+        emit!(StakesSwept { user, closed });
+
+        Ok(())
+    }
+
+    /// Keeper-callable end-of-life cleanup. Once the arena is Settled, every winner has been
+    /// paid and the claim expiry has passed, sweeps the vault's remaining lamports (fee
+    /// residue and rounding dust) to `fee_recipient` and closes the arena, returning its
+    /// rent to the creator.
+    pub fn expire_arena(ctx: Context<ExpireArena>) -> Result<()> {
+        let arena = &ctx.accounts.arena;
+        let now = Clock::get()?.unix_timestamp;
+        arena.require_status(ArenaStatus::Settled)?;
+        require!(!arena.in_dispute_window(now), SoliseumError::DisputeWindowOpen);
+        require!(arena.unclaimed_count == 0, SoliseumError::ClaimsOutstanding);
+        require!(
+            now >= arena.settled_at.saturating_add(arena.claim_expiry_secs),
+            SoliseumError::ClaimWindowOpen
+        );
+        require!(arena.reclaimable_house_bonus() == 0, SoliseumError::HouseBonusUnreclaimed);
+
+        let swept = sweep_vault(
+            &ctx.accounts.arena,
+            &ctx.accounts.vault,
+            &ctx.accounts.fee_recipient.to_account_info(),
+            &ctx.accounts.system_program,
+        )?;
+
+        emit!(ArenaExpired {
+            arena: ctx.accounts.arena.key(),
+            fee_recipient: ctx.accounts.fee_recipient.key(),
+            swept,
+        });
+
+        Ok(())
+    }
+
+    /// Keeper-callable once the claim deadline has passed: sends everything left in the vault
+    /// (unclaimed winnings, fees, referral cuts and dust) to `fee_recipient` and writes off
+    /// the outstanding claims so the arena can be closed. A house bonus the creator may still
+    /// reclaim is theirs rather than forfeited, so it must be reclaimed first.
+    pub fn sweep_unclaimed(ctx: Context<SweepUnclaimed>) -> Result<()> {
+        require!(
+            ctx.accounts.arena.claims_expired(Clock::get()?.unix_timestamp),
+            SoliseumError::ClaimWindowOpen
+        );
+        require!(
+            ctx.accounts.arena.reclaimable_house_bonus() == 0,
+            SoliseumError::HouseBonusUnreclaimed
+        );
+
+        let swept = sweep_vault(
+            &ctx.accounts.arena,
+            &ctx.accounts.vault,
+            &ctx.accounts.fee_recipient.to_account_info(),
+            &ctx.accounts.system_program,
+        )?;
+
+        let arena = &mut ctx.accounts.arena;
+        let forfeited = arena.unclaimed_count;
+        arena.unclaimed_count = 0;
+        arena.accrued_fees = 0;
+        arena.referral_owed = 0;
+        // The rent reserve leaves with the sweep
+        arena.vault_rent_reserve = 0;
+        arena.fees_withdrawn = arena.fees_withdrawn.checked_add(swept).ok_or(SoliseumError::MathOverflow)?;
+
+        emit!(UnclaimedSwept {
+            arena: ctx.accounts.arena.key(),
+            fee_recipient: ctx.accounts.fee_recipient.key(),
+            swept,
+            forfeited,
+        });
+
+        Ok(())
+    }
+
+    /// Creator-side counterpart of `expire_arena`: once a Settled or Cancelled arena owes
+    /// nothing more (no unclaimed stakes or house bonus, dispute window over), sweeps the
+    /// vault residue to `fee_recipient` and closes the arena, returning its rent to the creator.
+    pub fn close_arena(ctx: Context<CloseArena>) -> Result<()> {
+        let arena = &ctx.accounts.arena;
+        require!(
+            !arena.in_dispute_window(Clock::get()?.unix_timestamp),
+            SoliseumError::DisputeWindowOpen
+        );
+        require!(arena.unclaimed_count == 0, SoliseumError::ClaimsOutstanding);
+        require!(arena.referral_owed == 0, SoliseumError::ReferralUnclaimed);
+        require!(arena.reclaimable_house_bonus() == 0, SoliseumError::HouseBonusUnreclaimed);
+
+        let swept = sweep_vault(
+            &ctx.accounts.arena,
+            &ctx.accounts.vault,
+            &ctx.accounts.fee_recipient.to_account_info(),
+            &ctx.accounts.system_program,
+        )?;
+
+        emit!(ArenaClosed {
+            arena: ctx.accounts.arena.key(),
+            fee_recipient: ctx.accounts.fee_recipient.key(),
+            swept,
+        });
+
+        Ok(())
+    }
+
+    /// Creator-only: grows an arena of any earlier schema version in place to the current
+    /// layout and stamps ARENA_VERSION. Each version only appended fields, so the account
+    /// must hold at least its version's `space_at` and the resized tail is zero-filled.
+    /// From v1, the v2 fields get their defaults: creator staking allowed, a zero square-root
+    /// pool per outcome and one oracle group per seat. The v3 fields start at zero: no
+    /// minimum fee or side liquidity, committee settlement, no resets, relayers or house
+    /// bonus. Settled arenas have their settlement snapshot rebuilt. The creator pays any
+    /// extra rent, and an account is never shrunk.
+    /// Stakes of a v1 or v2 arena are v1 stakes, which no longer load, so such an arena only
+    /// migrates once no stake has anything left to do: fails with `ClaimsOutstanding` while it
+    /// is Active with stakes or owes any claim or referral.
+    pub fn migrate_arena(ctx: Context<MigrateArena>, _arena_id: u64) -> Result<()> {
+        let info = ctx.accounts.arena.to_account_info();
+        require_keys_eq!(*info.owner, *ctx.program_id, SoliseumError::InvalidArenaState);
+
+        let (from_version, old_len, new_len) = {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.len() > ARENA_OUTCOMES_OFFSET_BASE
+                    && data[..8] == <Arena as anchor_lang::Discriminator>::DISCRIMINATOR,
+                SoliseumError::InvalidArenaState
+            );
+            let from_version = data[8];
+            require!(
+                from_version >= 1 && from_version < ARENA_VERSION,
+                SoliseumError::UnsupportedVersion
+            );
+            // Every layout matches the current one up to `outcomes`, which sizes the account
+            let oracle_count = u32::from_le_bytes(
+                data[ARENA_ORACLES_OFFSET..ARENA_ORACLES_OFFSET + 4].try_into().unwrap(),
+            ) as usize;
+            require!(oracle_count <= MAX_ORACLES, SoliseumError::InvalidArenaState);
+            let outcomes_offset = ARENA_OUTCOMES_OFFSET_BASE + oracle_count * 32;
+            require!(data.len() > outcomes_offset, SoliseumError::InvalidArenaState);
+            let outcomes = data[outcomes_offset] as usize;
+            require!(
+                data.len() >= 8 + Arena::space_at(from_version, oracle_count, outcomes),
+                SoliseumError::InvalidArenaState
+            );
+            (from_version, data.len(), 8 + Arena::space(oracle_count, outcomes))
+        };
+        require!(new_len >= old_len, SoliseumError::InvalidArenaState);
+
+        let top_up = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(info.lamports());
+        if top_up > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.creator.to_account_info(),
+                to: info.clone(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            transfer(cpi_ctx, top_up)?;
+        }
+        info.realloc(new_len, true)?;
+
+        let mut arena = {
+            let data = info.try_borrow_data()?;
+            Arena::try_deserialize(&mut &data[..]).map_err(|_| error!(SoliseumError::InvalidArenaState))?
+        };
+        if from_version < 3 {
+            require!(
+                arena.unclaimed_count == 0
+                    && arena.referral_owed == 0
+                    && (arena.status != ArenaStatus::Active || arena.total_stakers()? == 0),
+                SoliseumError::ClaimsOutstanding
+            );
+        }
+        arena.version = ARENA_VERSION;
+        if from_version < 2 {
+            arena.allow_creator_stake = true;
+            arena.sqrt_pools = vec![0; arena.outcomes as usize];
+            arena.oracle_groups = core::array::from_fn(|i| i as u8);
+        }
+        if from_version < 4 {
+            // `stakers` used to hand out side_index and was never decremented
+            arena.next_side_index = arena.stakers.clone();
+        }
+        if matches!(arena.status, ArenaStatus::Settled | ArenaStatus::Disputable) {
+            // Claims read the settlement snapshot, which earlier layouts only partly cached
+            arena.cache_settlement()?;
+        }
+        {
+            let mut data = info.try_borrow_mut_data()?;
+            arena.try_serialize(&mut &mut data[..])?;
+        }
+
+        emit!(ArenaMigrated {
+            arena: info.key(),
+            from_version,
+            to_version: ARENA_VERSION,
+        });
+
+        Ok(())
+    }
+
+    /// Creator-only emergency unwind of one stake while the arena is Active: pays its
+    /// principal back to the owner and takes it out of the pools, leaving the rest of the
+    /// book untouched. Like a full `withdraw_stake`, the stake stays open at zero so its
+    /// `side_index` slot stays valid, leaves its side's staker count and is marked claimed.
+    /// Ignores `min_stake_lock_secs` and emits `StakeAdminRefunded` for the audit trail.
+    pub fn admin_refund_stake(ctx: Context<AdminRefundStake>) -> Result<()> {
+        let amount = ctx.accounts.stake.amount;
+        require!(amount > 0, SoliseumError::WithdrawalExceedsStake);
+        require!(
+            amount <= ctx.accounts.vault.lamports(),
+            SoliseumError::VaultUnderfunded
+        );
+        require!(
+            amount <= ctx.accounts.arena.spendable(ctx.accounts.vault.lamports()),
+            SoliseumError::RentReserveBreached
+        );
+
+        let side = ctx.accounts.stake.side;
+        let arena = &mut ctx.accounts.arena;
+        arena.total_pool = arena.total_pool.checked_sub(amount).ok_or(SoliseumError::MathOverflow)?;
+        let side_pool = &mut arena.pools[side as usize];
+        *side_pool = side_pool.checked_sub(amount).ok_or(SoliseumError::MathOverflow)?;
+        arena.reweigh(side, amount, 0)?;
+        let side_stakers = &mut arena.stakers[side as usize];
+        *side_stakers = side_stakers.checked_sub(1).ok_or(SoliseumError::MathOverflow)?;
+        ctx.accounts.stake.amount = 0;
+        ctx.accounts.stake.claimed = true;
+
+        let arena_key = ctx.accounts.arena.key();
+        pay_from_vault(
+            &ctx.accounts.arena,
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program,
+            amount,
+        )?;
+        emit!(StakeAdminRefunded {
+            arena: arena_key,
+            owner: ctx.accounts.owner.key(),
+            side,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Creator-only: opens a bracket with `first_arena`, one of the creator's arenas, as
+    /// round 0. Each round is a standalone arena, so staking and settlement stay with the
+    /// per-arena instructions; `advance_round` chains the next one on.
+    pub fn initialize_tournament(ctx: Context<InitializeTournament>, tournament_id: u64) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.creator = ctx.accounts.creator.key();
+        tournament.tournament_id = tournament_id;
+        tournament.arenas = vec![ctx.accounts.first_arena.key()];
+        tournament.current_round = 0;
+
+        emit!(RoundAdvanced {
+            tournament: tournament.key(),
+            round: 0,
+            arena: ctx.accounts.first_arena.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Creator-only: once the current round's arena is Settled, makes `next_arena` the next
+    /// round and lifts its pause, so a round created paused opens for staking only when the
+    /// bracket reaches it. Up to MAX_TOURNAMENT_ROUNDS rounds, each arena used once.
+    pub fn advance_round(ctx: Context<AdvanceRound>) -> Result<()> {
+        let tournament = &ctx.accounts.tournament;
+        require!(
+            tournament.arenas.len() < MAX_TOURNAMENT_ROUNDS,
+            SoliseumError::TournamentFull
+        );
+        let next_key = ctx.accounts.next_arena.key();
+        require!(
+            !tournament.arenas.contains(&next_key),
+            SoliseumError::InvalidTournamentArena
+        );
+
+        let next_arena = &mut ctx.accounts.next_arena;
+        if next_arena.paused {
+            next_arena.paused = false;
+            emit!(ArenaPauseChanged {
+                arena: next_key,
+                paused: false,
+            });
+        }
+
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.arenas.push(next_key);
+        tournament.current_round = tournament.current_round.checked_add(1).ok_or(SoliseumError::MathOverflow)?;
+
+        emit!(RoundAdvanced {
+            tournament: tournament.key(),
+            round: tournament.current_round,
+            arena: next_key,
+        });
+
+        Ok(())
+    }
+
+    /// Creator-only: closes an Active arena nobody has staked in, returning the arena's rent
+    /// and anything parked in the vault (e.g. a `fund_vault_rent` reserve) to the creator.
+    pub fn abandon_arena(ctx: Context<AbandonArena>) -> Result<()> {
+        let arena = &ctx.accounts.arena;
+        require!(
+            arena.total_pool == 0 && arena.stakes_opened()? == 0,
+            SoliseumError::StakingStarted
+        );
+
+        let swept = sweep_vault(
+            &ctx.accounts.arena,
+            &ctx.accounts.vault,
+            &ctx.accounts.creator.to_account_info(),
+            &ctx.accounts.system_program,
+        )?;
+
+        emit!(ArenaAbandoned {
+            arena: ctx.accounts.arena.key(),
+            creator: ctx.accounts.creator.key(),
+            swept,
+        });
+
+        Ok(())
+    }
+
+    /// Creator-only switch that freezes new stakes on an Active arena, e.g. while a match
+    /// is delayed or an oracle feed is unreliable. Claims and refunds are unaffected.
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        let arena = &mut ctx.accounts.arena;
+        arena.paused = paused;
+
+        emit!(ArenaPauseChanged {
+            arena: arena.key(),
+            paused,
+        });
+
+        Ok(())
+    }
+
+    /// Tops the vault up to the rent-exempt minimum for a zero-data account and parks that
+    /// amount as `vault_rent_reserve`. Anyone may pay. Claims, withdrawals and fee
+    /// withdrawals then never spend the reserve, so the vault balance can no longer land
+    /// between zero and the rent-exempt minimum, which the runtime rejects. Only the sweeps
+    /// that close out the vault take the reserve with them.
+    pub fn fund_vault_rent(ctx: Context<FundVaultRent>) -> Result<()> {
+        let minimum = Rent::get()?.minimum_balance(0);
+        let top_up = minimum.saturating_sub(ctx.accounts.arena.vault_rent_reserve);
+        if top_up == 0 {
+            return Ok(());
+        }
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.payer.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            cpi_accounts,
+        );
+        transfer(cpi_ctx, top_up)?;
+
+        let arena = &mut ctx.accounts.arena;
+        arena.vault_rent_reserve = minimum;
+
+        emit!(VaultTransfer {
+            arena: arena.key(),
+            direction: VaultDirection::Inflow,
+            amount: top_up,
+            counterparty: ctx.accounts.payer.key(),
+            total_pool: arena.total_pool,
+        });
+
+        Ok(())
+    }
+
+    /// Creator-only: tops `house_bonus` up to `house_bonus_bps` of the current `total_pool`,
+    /// paying the difference into the vault. Winners split the bonus pro-rata on top of the
+    /// loser pool, fee-free. Only callable once betting has closed, so the bonus matches the
+    /// final pool; a refunding outcome or a cancellation hands it back through
+    /// `reclaim_house_bonus`.
+    pub fn house_deposit(ctx: Context<HouseDeposit>) -> Result<()> {
+        let arena = &ctx.accounts.arena;
+        require!(
+            arena.betting_closes_at == 0 || Clock::get()?.unix_timestamp >= arena.betting_closes_at,
+            SoliseumError::BettingStillOpen
+        );
+        let target = mul_div(arena.total_pool, arena.house_bonus_bps as u64, BPS_DENOMINATOR)?;
+        let top_up = target.saturating_sub(arena.house_bonus);
+        if top_up == 0 {
+            return Ok(());
+        }
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.creator.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            cpi_accounts,
+        );
+        transfer(cpi_ctx, top_up)?;
+
+        let arena = &mut ctx.accounts.arena;
+        arena.house_bonus = target;
+
+        emit!(VaultTransfer {
+            arena: arena.key(),
+            direction: VaultDirection::Inflow,
+            amount: top_up,
+            counterparty: ctx.accounts.creator.key(),
+            total_pool: arena.total_pool,
+        });
+
+        Ok(())
+    }
+
+    /// Creator-only: returns `house_bonus` from the vault once no winner can claim it, i.e.
+    /// the arena was cancelled or settled on an outcome that refunds every stake.
+    pub fn reclaim_house_bonus(ctx: Context<ReclaimHouseBonus>) -> Result<()> {
+        let arena = &ctx.accounts.arena;
+        let amount = arena.house_bonus;
+        if amount == 0 {
+            return Ok(());
+        }
+        if arena.status == ArenaStatus::Settled {
+            // A refunding result may still be corrected to one with winners
+            require!(
+                !arena.in_dispute_window(Clock::get()?.unix_timestamp),
+                SoliseumError::DisputeWindowOpen
+            );
+            require!(arena.refunds_all(), SoliseumError::HouseBonusOwed);
+        }
+        require!(
+            amount <= ctx.accounts.arena.spendable(ctx.accounts.vault.lamports()),
+            SoliseumError::RentReserveBreached
+        );
+
+        pay_from_vault(
+            &ctx.accounts.arena,
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.creator.to_account_info(),
+            &ctx.accounts.system_program,
+            amount,
+        )?;
+
+        let arena = &mut ctx.accounts.arena;
+        arena.house_bonus = 0;
+
+        Ok(())
+    }
+
+    /// Creator-only: changes `fee_bps` while the arena is Active and nothing is staked (a new
+    /// arena or one freshly reset), so no stake's expected payout moves under it.
+    pub fn set_fee_bps(ctx: Context<SetFeeBps>, new_fee_bps: u16) -> Result<()> {
+        require!(new_fee_bps <= MAX_FEE_BPS, SoliseumError::MathOverflow);
+        let arena = &mut ctx.accounts.arena;
+        require!(arena.total_pool == 0, SoliseumError::StakingStarted);
+        require!(arena.referral_bps <= new_fee_bps, SoliseumError::InvalidReferralConfig);
+
+        let old_fee_bps = arena.fee_bps;
+        arena.fee_bps = new_fee_bps;
+
+        emit!(FeeBpsChanged {
+            arena: arena.key(),
+            old_fee_bps,
+            new_fee_bps,
+        });
+
+        Ok(())
+    }
+
+    /// Creator-only, before the first stake: funds each side's pool from the creator's wallet
+    /// (`amounts` has one entry per outcome) so early stakers face a two-sided book.
+    /// Seeded lamports back no `Stake`: they are never refunded, and the winning side's
+    /// seed is paid out to that side's stakers together with the losing pools.
+    pub fn seed_liquidity(ctx: Context<SeedLiquidity>, amounts: Vec<u64>) -> Result<()> {
+        let arena = &ctx.accounts.arena;
+        require!(amounts.len() == arena.outcomes as usize, SoliseumError::InvalidArenaState);
+        require!(
+            arena.total_pool == 0 && arena.stakes_opened()? == 0,
+            SoliseumError::StakingStarted
+        );
+        let total = amounts
+            .iter()
+            .try_fold(0u64, |sum, amount| sum.checked_add(*amount))
+            .ok_or(SoliseumError::MathOverflow)?;
+        require!(total > 0, SoliseumError::InvalidArenaState);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.creator.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            cpi_accounts,
+        );
+        transfer(cpi_ctx, total)?;
+
+        let arena = &mut ctx.accounts.arena;
+        arena.pools = amounts.clone();
+        arena.seeded = amounts.clone();
+        arena.total_pool = total;
+
+        emit!(VaultTransfer {
+            arena: arena.key(),
+            direction: VaultDirection::Inflow,
+            amount: total,
+            counterparty: ctx.accounts.creator.key(),
+            total_pool: total,
+        });
+        emit!(LiquiditySeeded {
+            arena: arena.key(),
+            amounts,
+        });
+
+        Ok(())
+    }
+
+    /// Read-only payout preview for a user's stake, via `compute_payout`. On a settled arena
+    /// it is exactly what `claim_reward` would pay (losing stakes are rejected); before
+    /// settlement it is what the stake would receive if its side won at the current pools.
+    /// A cancelled arena refunds the stake in full.
+    pub fn quote_payout(ctx: Context<QuotePayout>) -> Result<u64> {
+        let arena = &ctx.accounts.arena;
+        let stake = load_stake(
+            &ctx.accounts.stake.to_account_info(),
+            ctx.program_id,
+            &arena.key(),
+            arena.epoch,
+            &ctx.accounts.user.key(),
+        )?;
+
+        match arena.status {
+            ArenaStatus::Settled | ArenaStatus::Disputable => settled_payout(arena, &stake),
+            ArenaStatus::Cancelled => Ok(stake.amount),
+            ArenaStatus::Pending | ArenaStatus::Active => {
+                let (side_pool, other_pools) = arena.winner_and_loser_pools(stake.side)?;
+                let sqrt_pool = arena.sqrt_pools[stake.side as usize];
+                let payout = mode_payout(
+                    arena.payout_mode,
+                    stake.amount,
+                    side_pool,
+                    sqrt_pool,
+                    arena.net_after_fees(other_pools)?,
+                )?;
+                let bonus = house_bonus_share(arena.payout_mode, stake.amount, side_pool, sqrt_pool, arena.house_bonus)?;
+                Ok(payout.checked_add(bonus).ok_or(SoliseumError::MathOverflow)?)
+            }
+        }
+    }
+
+    /// Read-only: the lamports `claim_reward` would transfer for this stake right now,
+    /// including the rounding dust owed to the last claimant. 0 when there is nothing to
+    /// claim: already claimed, not settled, a losing side, expired, or paid in tokens.
+    pub fn get_claimable(ctx: Context<GetClaimable>) -> Result<u64> {
+        let arena = &ctx.accounts.arena;
+        let stake = load_stake(
+            &ctx.accounts.stake.to_account_info(),
+            ctx.program_id,
+            &arena.key(),
+            arena.epoch,
+            &ctx.accounts.user.key(),
+        )?;
+
+        let claimable = arena.status == ArenaStatus::Settled
+            && !stake.claimed
+            && arena.stake_mint.is_none()
+            && (arena.payout_mint.is_none() || arena.refunds_all())
+            && (arena.refunds_all() || arena.winner == Some(stake.side))
+            && !arena.claims_expired(Clock::get()?.unix_timestamp);
+        if !claimable {
+            return Ok(0);
+        }
+
+        let mut payout = settled_payout(arena, &stake)?
+            .checked_sub(stake.claimed_amount)
+            .ok_or(SoliseumError::MathOverflow)?;
+        if arena.unclaimed_count == 1 && !arena.refunds_all() {
+            let fee = settled_fee(arena, &stake)?;
+            let dust = claim_dust(arena, payout, fee, ctx.accounts.vault.lamports());
+            payout = payout.checked_add(dust).ok_or(SoliseumError::MathOverflow)?;
+        }
+        Ok(payout)
+    }
+
+    /// Read-only: each side's implied probability in bps (`pools[i] / total_pool`), indexed
+    /// like `pools`. The entries always sum to 10_000; an empty pool splits evenly.
+    pub fn get_odds(ctx: Context<GetOdds>) -> Result<Vec<u16>> {
+        let arena = &ctx.accounts.arena;
+        implied_probabilities_bps(&arena.pools, arena.total_pool)
+    }
+
+    /// Read-only: status, pools, odds, result and timestamps of the arena in one return
+    /// value, so dashboards need not fetch and decode the account themselves.
+    pub fn get_arena_summary(ctx: Context<GetArenaSummary>) -> Result<ArenaSummary> {
+        let arena = &ctx.accounts.arena;
+        Ok(ArenaSummary {
+            status: arena.status.clone(),
+            paused: arena.paused,
+            pools: arena.pools.clone(),
+            total_pool: arena.total_pool,
+            total_stakers: arena.total_stakers()?,
+            odds_bps: implied_probabilities_bps(&arena.pools, arena.total_pool)?,
+            winner: arena.winner,
+            settlement_nonce: arena.settlement_nonce,
+            created_at: arena.created_at,
+            betting_opens_at: arena.betting_opens_at,
+            betting_closes_at: arena.betting_closes_at,
+            settled_at: arena.settled_at,
+            claim_deadline: arena.claim_deadline,
+        })
+    }
+
+    /// Read-only accounting check for off-chain monitors. Never fails on a violation;
+    /// returns a bitmask of `INVARIANT_*` flags instead (0 = healthy).
+    pub fn check_invariants(ctx: Context<CheckInvariants>) -> Result<u8> {
+        let arena = &ctx.accounts.arena;
+        let mut violations = 0u8;
+
+        let pool_sum = arena.pools.iter().try_fold(0u64, |sum, pool| sum.checked_add(*pool));
+        if pool_sum != Some(arena.total_pool) {
+            violations |= INVARIANT_POOL_MISMATCH;
+        }
+
+        // While staking is open every staked lamport is still owed; after settlement
+        // the vault must not be empty while winners remain unpaid.
+        // Token-staked arenas hold their pool in the vault's token account, not in lamports
+        let vault_lamports = ctx.accounts.vault.lamports();
+        let underfunded = arena.stake_mint.is_none() && match arena.status {
+            ArenaStatus::Pending | ArenaStatus::Active | ArenaStatus::Cancelled => {
+                vault_lamports < arena.total_pool
+            }
+            ArenaStatus::Settled | ArenaStatus::Disputable => {
+                arena.unclaimed_count > 0 && arena.spendable(vault_lamports) == 0
+            }
+        };
+        if underfunded {
+            violations |= INVARIANT_VAULT_UNDERFUNDED;
+        }
+
+        let winner_coherent = match (&arena.status, arena.winner) {
+            (ArenaStatus::Settled | ArenaStatus::Disputable, Some(winner)) => {
+                Outcome::from_winner(winner, arena.outcomes).is_some()
+            }
+            (ArenaStatus::Settled | ArenaStatus::Disputable, None) => false,
+            (_, winner) => winner.is_none(),
+        };
+        if !winner_coherent {
+            violations |= INVARIANT_WINNER_MISMATCH;
+        }
+
+        // A cancelled arena owes a refund to every staker on every side
+        let owed_stakers = match arena.status {
+            ArenaStatus::Cancelled => arena.stakers.iter().fold(0u32, |sum, n| sum.saturating_add(*n)),
+            _ => arena.owed_stakers(),
+        };
+        if arena.unclaimed_count > owed_stakers {
+            violations |= INVARIANT_UNCLAIMED_MISMATCH;
+        }
+
+        Ok(violations)
+    }
+}
+
+// Helper functions (outside #[program] block)
+
+/// Common head of every oracle-signed message: the action tag, then the program id and the
+/// arena's `cluster_id`, so a signature is only valid for one deployment on one cluster,
+/// then the arena address. Callers append the action's fields and the settlement nonce.
+fn message_header(tag: &[u8], program_id: &Pubkey, cluster_id: u8, arena: &Pubkey, extra: usize) -> Vec<u8> {
+    let mut msg = Vec::with_capacity(tag.len() + 32 + 1 + 32 + extra + 8);
+    msg.extend_from_slice(tag);
+    msg.extend_from_slice(&program_id.to_bytes());
+    msg.push(cluster_id);
+    msg.extend_from_slice(&arena.to_bytes());
+    msg
+}
+
+/// A non-zero `outcome_hash` is appended after the nonce; a zero hash leaves the legacy
+/// layout untouched so existing oracle signers keep working.
+fn create_settlement_message(
+    program_id: &Pubkey,
+    cluster_id: u8,
+    arena: &Pubkey,
+    winner: u8,
+    nonce: u64,
+    outcome_hash: &[u8; 32],
+) -> Vec<u8> {
+    let mut msg = message_header(b"soliseum:settle:", program_id, cluster_id, arena, 1 + 32);
+    msg.push(winner);
+    msg.extend_from_slice(&nonce.to_le_bytes());
+    if *outcome_hash != [0; 32] {
+        msg.extend_from_slice(outcome_hash);
+    }
+    msg
+}
+
+fn create_draw_message(program_id: &Pubkey, cluster_id: u8, arena: &Pubkey, nonce: u64) -> Vec<u8> {
+    let mut msg = message_header(b"soliseum:draw:", program_id, cluster_id, arena, 0);
+    msg.extend_from_slice(&nonce.to_le_bytes());
+    msg
+}
+
+fn create_correction_message(
+    program_id: &Pubkey,
+    cluster_id: u8,
+    arena: &Pubkey,
+    old_winner: u8,
+    new_winner: u8,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut msg = message_header(b"soliseum:correct:", program_id, cluster_id, arena, 2);
+    msg.push(old_winner);
+    msg.push(new_winner);
+    msg.extend_from_slice(&nonce.to_le_bytes());
+    msg
+}
+
+fn create_reset_message(program_id: &Pubkey, cluster_id: u8, arena: &Pubkey, nonce: u64) -> Vec<u8> {
+    let mut msg = message_header(b"soliseum:reset:", program_id, cluster_id, arena, 0);
+    msg.extend_from_slice(&nonce.to_le_bytes());
+    msg
+}
+
+fn create_cancel_message(program_id: &Pubkey, cluster_id: u8, arena: &Pubkey, nonce: u64) -> Vec<u8> {
+    let mut msg = message_header(b"soliseum:cancel:", program_id, cluster_id, arena, 0);
+    msg.extend_from_slice(&nonce.to_le_bytes());
+    msg
+}
+
+fn create_dispute_message(program_id: &Pubkey, cluster_id: u8, arena: &Pubkey, nonce: u64) -> Vec<u8> {
+    let mut msg = message_header(b"soliseum:dispute:", program_id, cluster_id, arena, 0);
+    msg.extend_from_slice(&nonce.to_le_bytes());
+    msg
+}
+
+fn create_oracle_update_message(
+    program_id: &Pubkey,
+    cluster_id: u8,
+    arena: &Pubkey,
+    new_oracles: &[Pubkey],
+    nonce: u64,
+) -> Vec<u8> {
+    let mut msg = message_header(
+        b"soliseum:update_oracles:",
+        program_id,
+        cluster_id,
+        arena,
+        new_oracles.len() * 32,
+    );
+    for oracle in new_oracles.iter() {
+        msg.extend_from_slice(&oracle.to_bytes());
+    }
+    msg.extend_from_slice(&nonce.to_le_bytes());
+    msg
+}
+
+fn create_seat_rotation_message(
+    program_id: &Pubkey,
+    cluster_id: u8,
+    arena: &Pubkey,
+    index: u8,
+    old_oracle: &Pubkey,
+    new_oracle: &Pubkey,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut msg = message_header(b"soliseum:rotate_seat:", program_id, cluster_id, arena, 1 + 64);
+    msg.push(index);
+    msg.extend_from_slice(&old_oracle.to_bytes());
+    msg.extend_from_slice(&new_oracle.to_bytes());
+    msg.extend_from_slice(&nonce.to_le_bytes());
+    msg
+}
+
+/// Everything `settle_game` checks before looking at signatures: winner in range, arena
+/// Active, betting closed, lockout over, out of matchmaking and enough participants.
+/// Returns the winner byte.
+fn check_settleable(arena: &Arena, winner: u8, now: i64) -> Result<u8> {
+    let winner = Outcome::from_side(winner, arena.outcomes)
+        .ok_or(SoliseumError::InvalidArenaState)?
+        .to_u8();
+    arena.require_status(ArenaStatus::Active)?;
+    require!(
+        arena.betting_closes_at == 0 || now >= arena.betting_closes_at,
+        SoliseumError::BettingStillOpen
+    );
+    require!(
+        !arena.in_settlement_lockout(now),
+        SoliseumError::SettlementLockedOut
+    );
+    require!(!arena.in_matchmaking(), SoliseumError::MarketNotReady);
+    require!(
+        arena.total_stakers()? >= arena.min_participants as u32,
+        SoliseumError::NotEnoughParticipants
+    );
+    Ok(winner)
+}
+
+/// Verifies the committee's signatures on a winner settlement at the arena's current
+/// nonce and returns the bitmask of accepted signers.
+fn verify_settlement(
+    arena: &Arena,
+    program_id: &Pubkey,
+    arena_key: &Pubkey,
+    winner: u8,
+    result_hash: &[u8; 32],
+    signatures: &[OracleSignature],
+) -> Result<u8> {
+    // Signatures cover arena address + winner + nonce (prevents replay attacks) + outcome hash
+    let message = create_settlement_message(
+        program_id,
+        arena.cluster_id,
+        arena_key,
+        winner,
+        arena.settlement_nonce,
+        result_hash,
+    );
+    verify_oracle_signatures(arena, &message, signatures)
+}
+
+/// Records a verified result and emits its event. A one-sided book is voided (cancelled
+/// for refunds) rather than settled; returns false in that case.
+fn apply_settlement(
+    arena: &mut Arena,
+    arena_key: Pubkey,
+    winner: u8,
+    signers: u8,
+    result_hash: [u8; 32],
+    now: i64,
+) -> Result<bool> {
+    let settlement_nonce = arena.settlement_nonce;
+    arena.settlement_nonce = settlement_nonce.checked_add(1).ok_or(SoliseumError::MathOverflow)?;
+
+    if arena.one_sided() {
+        arena.status = ArenaStatus::Cancelled;
+        arena.unclaimed_count = arena.total_stakers()?;
+
+        emit!(ArenaVoided {
+            arena: arena_key,
+            nonce: settlement_nonce,
+            signers,
+        });
+        return Ok(false);
+    }
+
+    arena.winner = Some(winner);
+    arena.result_hash = result_hash;
+    arena.status = arena.settled_status();
+    arena.cache_settlement()?;
+    // With nobody on the winning side there is no one to pay, so everyone is refunded
+    arena.unclaimed_count = arena.owed_stakers();
+    arena.settled_at = now;
+    arena.set_claim_deadline()?;
+    arena.record_signers(signers);
+
+    emit!(GameSettled {
+        arena: arena_key,
+        winner,
+        nonce: settlement_nonce,
+        signers,
+        result_hash,
+    });
+    Ok(true)
+}
+
+/// Numeric code of an error, as reported in events that record a skipped item
+fn error_code_number(err: &Error) -> u32 {
+    match err {
+        Error::AnchorError(err) => err.error_code_number,
+        Error::ProgramError(_) => 0,
+    }
+}
+
+/// Checks that `signatures` come from at least `oracle_threshold` distinct committee members
+/// and are all valid over `message`. Duplicates are caught with a bitmask over
+/// `oracle_index` (MAX_ORACLES fits in a u8), so the check needs no allocation; the mask
+/// of signers is returned for the settlement record. More signatures than the committee
+/// has members are rejected before any verification work.
+fn verify_oracle_signatures(arena: &Arena, message: &[u8], signatures: &[OracleSignature]) -> Result<u8> {
+    require!(
+        signatures.len() <= arena.oracles.len(),
+        SoliseumError::TooManySignatures
+    );
+    require!(
+        signatures.len() >= arena.oracle_threshold as usize,
+        SoliseumError::InsufficientSignatures
+    );
+
+    let mut used: u8 = 0;
+    for sig in signatures {
+        require!(
+            (sig.oracle_index as usize) < arena.oracles.len(),
+            SoliseumError::InvalidOracleIndex
+        );
+        let bit = 1u8 << sig.oracle_index;
+        require!(used & bit == 0, SoliseumError::DuplicateOracle);
+        used |= bit;
+
+        require!(
+            verify_ed25519_signature(&arena.oracles[sig.oracle_index as usize], message, &sig.signature),
+            SoliseumError::InvalidSignature
+        );
+    }
+    Ok(used)
+}
+
+/// Adds to the `GlobalConfig` aggregates when its PDA is the first of `accounts`; callers
+/// that leave it out skip the update.
+fn record_global_stats<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    program_id: &Pubkey,
+    arenas: u64,
+    volume_lamports: u64,
+    fees_collected: u64,
+) -> Result<()> {
+    let info = match accounts.first() {
+        Some(info) => info,
+        None => return Ok(()),
+    };
+    let (expected, _) = Pubkey::find_program_address(&[b"config"], program_id);
+    require_keys_eq!(info.key(), expected, SoliseumError::InvalidGlobalConfig);
+
+    let mut config = Account::<GlobalConfig>::try_from(info)?;
+    config.total_arenas = config.total_arenas.checked_add(arenas).ok_or(SoliseumError::MathOverflow)?;
+    config.total_volume_lamports = config
+        .total_volume_lamports
+        .checked_add(volume_lamports)
+        .ok_or(SoliseumError::MathOverflow)?;
+    config.total_fees_collected = config
+        .total_fees_collected
+        .checked_add(fees_collected)
+        .ok_or(SoliseumError::MathOverflow)?;
+    config.exit(program_id)?;
+
+    emit!(GlobalStatsUpdated {
+        total_arenas: config.total_arenas,
+        total_volume_lamports: config.total_volume_lamports,
+        total_fees_collected: config.total_fees_collected,
+    });
+    Ok(())
+}
+
+/// Bumps the `OracleStats` PDAs in `accounts` for the committee members in `mask`: a
+/// signed settlement, or a lost dispute when the result they signed is overturned. Each
+/// account must belong to a member in `mask` and may appear only once.
+fn credit_oracle_stats<'info>(
+    arena: &Arena,
+    mask: u8,
+    accounts: &'info [AccountInfo<'info>],
+    program_id: &Pubkey,
+    dispute_lost: bool,
+) -> Result<()> {
+    let mut credited: u8 = 0;
+    for info in accounts {
+        let mut stats = load_oracle_stats(info, program_id)?;
+        let index = arena
+            .oracles
+            .iter()
+            .position(|pk| *pk == stats.oracle)
+            .ok_or(SoliseumError::InvalidOracleStats)?;
+        let bit = 1u8 << index;
+        require!(mask & bit != 0 && credited & bit == 0, SoliseumError::InvalidOracleStats);
+        credited |= bit;
+
+        let counter = if dispute_lost {
+            &mut stats.disputes_lost
+        } else {
+            &mut stats.settlements_signed
+        };
+        *counter = counter.checked_add(1).ok_or(SoliseumError::MathOverflow)?;
+        stats.exit(program_id)?;
+    }
+    Ok(())
+}
+
+/// `credit_oracle_stats` for one arena of `settle_games_batch`. `accounts` holds the stats
+/// of every arena's signers, so those of oracles outside this arena's `mask` are skipped
+/// rather than rejected; each is still checked to be an `OracleStats` PDA.
+fn credit_batch_oracle_stats<'info>(
+    arena: &Arena,
+    mask: u8,
+    accounts: &'info [AccountInfo<'info>],
+    program_id: &Pubkey,
+) -> Result<()> {
+    let mut credited: u8 = 0;
+    for info in accounts {
+        let mut stats = load_oracle_stats(info, program_id)?;
+        let bit = match arena.oracles.iter().position(|pk| *pk == stats.oracle) {
+            Some(index) => 1u8 << index,
+            None => continue,
+        };
+        if mask & bit == 0 {
+            continue;
+        }
+        require!(credited & bit == 0, SoliseumError::InvalidOracleStats);
+        credited |= bit;
+
+        stats.settlements_signed = stats.settlements_signed.checked_add(1).ok_or(SoliseumError::MathOverflow)?;
+        stats.exit(program_id)?;
+    }
+    Ok(())
+}
+
+/// Loads an `OracleStats` account from `remaining_accounts`, checking it sits at its PDA.
+fn load_oracle_stats<'info>(info: &'info AccountInfo<'info>, program_id: &Pubkey) -> Result<Account<'info, OracleStats>> {
+    let stats = Account::<OracleStats>::try_from(info)?;
+    let (expected, _) = Pubkey::find_program_address(&[b"oracle_stats", stats.oracle.as_ref()], program_id);
+    require_keys_eq!(info.key(), expected, SoliseumError::InvalidOracleStats);
+    Ok(stats)
+}
+
+/// A committee needs 1 to MAX_ORACLES distinct, non-default keys.
+fn validate_committee(oracles: &[Pubkey]) -> Result<()> {
+    require!(
+        !oracles.is_empty() && oracles.len() <= MAX_ORACLES,
+        SoliseumError::InvalidOracleConfig
+    );
+    require!(
+        oracles.iter().all(|pk| *pk != Pubkey::default()),
+        SoliseumError::InvalidOracleConfig
+    );
+    for i in 0..oracles.len() {
+        for j in (i + 1)..oracles.len() {
+            require!(oracles[i] != oracles[j], SoliseumError::InvalidOracleConfig);
+        }
+    }
+    Ok(())
+}
+
+/// Payout multiplier in bps (10_000 = 1x) a side would pay before fees: total / side pool.
+fn implied_multiplier_bps(total_pool: u64, side_pool: u64) -> Result<u64> {
+    require!(side_pool > 0, SoliseumError::MathOverflow);
+    mul_div(total_pool, BPS_DENOMINATOR, side_pool)
+}
+
+/// Implied probability of each pool in bps. Flooring leaves up to `pools.len() - 1` bps
+/// unassigned; they go to the largest pool (the first on ties) so the result sums to
+/// 10_000. With nothing staked every side gets an even share, remainder to side 0.
+fn implied_probabilities_bps(pools: &[u64], total_pool: u64) -> Result<Vec<u16>> {
+    require!(!pools.is_empty(), SoliseumError::InvalidArenaState);
+    let mut odds = if total_pool == 0 {
+        vec![(BPS_DENOMINATOR / pools.len() as u64) as u16; pools.len()]
+    } else {
+        pools
+            .iter()
+            .map(|&pool| {
+                let bps = mul_div(pool, BPS_DENOMINATOR, total_pool)?;
+                Ok(u16::try_from(bps).map_err(|_| SoliseumError::MathOverflow)?)
+            })
+            .collect::<Result<Vec<u16>>>()?
+    };
+    let assigned: u64 = odds.iter().map(|&bps| bps as u64).sum();
+    let remainder = BPS_DENOMINATOR.checked_sub(assigned).ok_or(SoliseumError::MathOverflow)?;
+    let largest = pools
+        .iter()
+        .enumerate()
+        .fold(0, |best, (i, &pool)| if pool > pools[best] { i } else { best });
+    odds[largest] += remainder as u16;
+    Ok(odds)
+}
+
+/// Validates a stake of `amount` on `side` and records it on the arena and stake,
+/// returning the new total pool. All checks run before any field is written; callers
+/// move the funds afterwards, and a failed transfer reverts the whole instruction.
+fn record_stake(
+    arena: &mut Arena,
+    stake: &mut Stake,
+    user: Pubkey,
+    amount: u64,
+    side: u8,
+    min_acceptable_odds_bps: Option<u64>,
+    max_implied_prob_bps: Option<u16>,
+    referrer: Option<Pubkey>,
+) -> Result<u64> {
+    Outcome::from_side(side, arena.outcomes).ok_or(SoliseumError::InvalidArenaState)?;
+    require!(referrer != Some(user), SoliseumError::InvalidReferrer);
+    require!(
+        arena.allow_creator_stake || user != arena.creator,
+        SoliseumError::CreatorStakeForbidden
+    );
+    arena.require_status(ArenaStatus::Active)?;
+    require!(!arena.paused, SoliseumError::ArenaPaused);
+    require!(
+        arena.betting_open(Clock::get()?.unix_timestamp),
+        SoliseumError::BettingClosed
+    );
+    require!(amount > 0, SoliseumError::MathOverflow);
+    require!(
+        arena.stake_increment == 0 || amount % arena.stake_increment == 0,
+        SoliseumError::InvalidStakeIncrement
+    );
+
+    // A fully withdrawn stake keeps its side and side_index, so "new" means freshly created;
+    // topping one up brings it back into the side's staker count
+    let is_new_stake = stake.owner == Pubkey::default();
+    let joins_side = is_new_stake || stake.amount == 0;
+    if !is_new_stake {
+        require!(stake.version == STAKE_VERSION, SoliseumError::UnsupportedVersion);
+        require!(stake.side == side, SoliseumError::InvalidArenaState);
+        require!(
+            referrer.is_none() || referrer == stake.referrer,
+            SoliseumError::InvalidReferrer
+        );
+    }
+    require!(amount >= arena.min_stake, SoliseumError::StakeTooSmall);
+    let new_stake_amount = stake.amount.checked_add(amount).ok_or(SoliseumError::MathOverflow)?;
+    require!(
+        arena.max_stake_per_user == 0 || new_stake_amount <= arena.max_stake_per_user,
+        SoliseumError::StakeLimitExceeded
+    );
+
+    let new_total_pool = arena.total_pool.checked_add(amount).ok_or(SoliseumError::MathOverflow)?;
+    require!(
+        arena.max_total_pool == 0 || new_total_pool <= arena.max_total_pool,
+        SoliseumError::PoolCapExceeded
+    );
+    let (side_pool, side_stakers) = (arena.pools[side as usize], arena.stakers[side as usize]);
+    let new_side_pool = side_pool.checked_add(amount).ok_or(SoliseumError::MathOverflow)?;
+    // Until another side has any stake every share is 100%, so the cap only bites after
+    require!(
+        arena.max_side_share_bps == 0
+            || new_side_pool == new_total_pool
+            || (new_side_pool as u128) * (BPS_DENOMINATOR as u128)
+                <= (new_total_pool as u128) * (arena.max_side_share_bps as u128),
+        SoliseumError::SideShareExceeded
+    );
+    if let Some(min_odds_bps) = min_acceptable_odds_bps {
+        require!(
+            implied_multiplier_bps(new_total_pool, new_side_pool.saturating_sub(arena.seed(side)))? >= min_odds_bps,
+            SoliseumError::OddsSlippage
+        );
+    }
+    if let Some(max_prob_bps) = max_implied_prob_bps {
+        require!(
+            mul_div(new_side_pool, BPS_DENOMINATOR, new_total_pool)? <= max_prob_bps as u64,
+            SoliseumError::OddsSlippageExceeded
+        );
+    }
+    let new_side_stakers = if joins_side {
+        side_stakers.checked_add(1).ok_or(SoliseumError::MathOverflow)?
+    } else {
+        side_stakers
+    };
+
+    if is_new_stake {
+        let side_index = arena.next_side_index[side as usize];
+        arena.next_side_index[side as usize] = side_index.checked_add(1).ok_or(SoliseumError::MathOverflow)?;
+        stake.version = STAKE_VERSION;
+        stake.owner = user;
+        stake.side = side;
+        stake.side_index = side_index;
+        stake.referrer = referrer;
+        stake.referral_owed = 0;
+        stake.claimed_amount = 0;
+        stake.placed_at = Clock::get()?.unix_timestamp;
+        stake.epoch = arena.epoch;
+    }
+    // A withdrawn stake was marked done so settlement would not wait on it
+    stake.claimed = false;
+
+    arena.reweigh(side, stake.amount, new_stake_amount)?;
+    stake.amount = new_stake_amount;
+
+    arena.last_stake_at = Clock::get()?.unix_timestamp;
+    arena.total_pool = new_total_pool;
+    arena.pools[side as usize] = new_side_pool;
+    arena.stakers[side as usize] = new_side_stakers;
+
+    Ok(new_total_pool)
+}
+
+/// Pays `amount` lamports from the arena's vault to `to`, signing as the vault PDA, and
+/// emits the matching `VaultTransfer`.
+fn pay_from_vault<'info>(
+    arena: &Account<'info, Arena>,
+    vault: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    let arena_key = arena.key();
+    let vault_seeds = &[
+        b"vault",
+        arena_key.as_ref(),
+        &[arena.vault_bump],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: vault.clone(),
+        to: to.clone(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        cpi_accounts,
+        vault_signer,
+    );
+    transfer(cpi_ctx, amount)?;
+
+    emit!(VaultTransfer {
+        arena: arena_key,
+        direction: VaultDirection::Outflow,
+        amount,
+        counterparty: to.key(),
+        total_pool: arena.total_pool,
+    });
+    Ok(())
+}
+
+/// Token counterpart of `pay_from_vault`: moves `amount` from the vault-owned token
+/// account `from` to `to`, with the vault PDA signing as their authority.
+fn pay_tokens_from_vault<'info>(
+    arena: &Account<'info, Arena>,
+    vault: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let arena_key = arena.key();
+    let vault_seeds = &[
+        b"vault",
+        arena_key.as_ref(),
+        &[arena.vault_bump],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+
+    let cpi_accounts = token::Transfer {
+        from: from.clone(),
+        to: to.clone(),
+        authority: vault.clone(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_accounts,
+        vault_signer,
+    );
+    token::transfer(cpi_ctx, amount)
+}
+
+/// Moves every lamport left in the arena's vault to `to`, returning the amount swept.
+fn sweep_vault<'info>(
+    arena: &Account<'info, Arena>,
+    vault: &UncheckedAccount<'info>,
+    to: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<u64> {
+    let swept = vault.lamports();
+    if swept == 0 {
+        return Ok(0);
+    }
+
+    pay_from_vault(arena, &vault.to_account_info(), to, system_program, swept)?;
+
+    Ok(swept)
+}
+
+/// Rejects stakers outside the arena's allowlist; an arena with a zero root is open.
+fn check_allowlist(arena: &Arena, user: &Pubkey, proof: Option<&[[u8; 32]]>) -> Result<()> {
+    if arena.whitelist_root == [0u8; 32] {
+        return Ok(());
+    }
+    let proof = proof.ok_or(SoliseumError::NotWhitelisted)?;
+    require!(
+        verify_allowlist_proof(&arena.whitelist_root, user, proof),
+        SoliseumError::NotWhitelisted
+    );
+    Ok(())
+}
+
+/// True when `proof` links `user` to `root`. Leaves are `sha256(user)` and each level
+/// hashes the sorted pair of nodes, so proofs need no left/right flags.
+pub fn verify_allowlist_proof(root: &[u8; 32], user: &Pubkey, proof: &[[u8; 32]]) -> bool {
+    let mut node = hashv(&[user.as_ref()]).to_bytes();
+    for sibling in proof {
+        node = if node <= *sibling {
+            hashv(&[node.as_ref(), sibling.as_ref()])
+        } else {
+            hashv(&[sibling.as_ref(), node.as_ref()])
+        }
+        .to_bytes();
+    }
+    node == *root
+}
+
+/// Marks a settled SOL stake claimed, accrues its protocol fee and returns what to pay it.
+/// The last stake to claim also takes the rounding dust the truncating payout division
+/// left in the vault; accrued protocol fees stay behind for `withdraw_fees`. Refunds are
+/// exact, so they take no dust and a forfeited seed stays in the vault too.
+fn record_claim(arena: &mut Arena, stake: &mut Stake, vault_lamports: u64) -> Result<u64> {
+    let remaining = settled_payout(arena, stake)?
+        .checked_sub(stake.claimed_amount)
+        .ok_or(SoliseumError::MathOverflow)?;
+    record_partial_claim(arena, stake, vault_lamports, remaining)
+}
+
+/// Books `amount` of a stake's settled payout as paid, returning the lamports to transfer.
+/// The chunk that completes the payout books the fee, marks the stake claimed and, for the
+/// last claimant, adds the vault's dust.
+fn record_partial_claim(arena: &mut Arena, stake: &mut Stake, vault_lamports: u64, amount: u64) -> Result<u64> {
+    require!(
+        !arena.claims_expired(Clock::get()?.unix_timestamp),
+        SoliseumError::ClaimExpired
+    );
+    let total_payout = settled_payout(arena, stake)?;
+    let claimed_amount = stake.claimed_amount.checked_add(amount).ok_or(SoliseumError::MathOverflow)?;
+    require!(claimed_amount <= total_payout, SoliseumError::ClaimExceedsPayout);
+    require!(amount <= vault_lamports, SoliseumError::VaultUnderfunded);
+    require!(
+        amount <= arena.spendable(vault_lamports),
+        SoliseumError::RentReserveBreached
+    );
+
+    stake.claimed_amount = claimed_amount;
+    let mut payout = amount;
+    if claimed_amount == total_payout {
+        accrue_fee(arena, stake)?;
+        stake.claimed = true;
+        arena.unclaimed_count = arena.unclaimed_count.checked_sub(1).ok_or(SoliseumError::MathOverflow)?;
+
+        if arena.unclaimed_count == 0 && !arena.refunds_all() {
+            let dust = claim_dust(arena, payout, 0, vault_lamports);
+            payout = payout.checked_add(dust).ok_or(SoliseumError::MathOverflow)?;
+        }
+    }
+    Ok(payout)
+}
+
+/// What the vault holds beyond the last payout and everything owed to fees and referrers;
+/// `pending_fee` covers a fee not yet booked into `accrued_fees`/`referral_owed`.
+fn claim_dust(arena: &Arena, payout: u64, pending_fee: u64, vault_lamports: u64) -> u64 {
+    arena
+        .spendable(vault_lamports)
+        .saturating_sub(payout)
+        .saturating_sub(arena.accrued_fees)
+        .saturating_sub(arena.referral_owed)
+        .saturating_sub(pending_fee)
+}
+
+/// Books the fee withheld from a stake's SOL claim: the referral cut, if the stake has a
+/// referrer, is owed on the stake, the rest accrues to the protocol.
+fn accrue_fee(arena: &mut Arena, stake: &mut Stake) -> Result<()> {
+    let fee = settled_fee(arena, stake)?;
+    let referral = match stake.referrer {
+        // A fee that only comes from the min_fee_lamports floor has no bps to split
+        Some(_) if fee > 0 && arena.settled_fee_bps > 0 => {
+            // Fee tiers may undercut referral_bps; the referrer never gets more than the fee
+            let fee_bps = arena.settled_fee_bps;
+            let referral_bps = arena.referral_bps.min(fee_bps);
+            mul_div(fee, referral_bps as u64, fee_bps as u64)?
+        }
+        _ => 0,
+    };
+
+    stake.referral_owed = stake.referral_owed.checked_add(referral).ok_or(SoliseumError::MathOverflow)?;
+    arena.referral_owed = arena.referral_owed.checked_add(referral).ok_or(SoliseumError::MathOverflow)?;
+    arena.accrued_fees = arena
+        .accrued_fees
+        .checked_add(fee - referral)
+        .ok_or(SoliseumError::MathOverflow)?;
+    Ok(())
+}
+
+/// `close_stake`'s rules for a finished stake; see there.
+fn check_stake_closable(arena: &Arena, stake: &Stake, now: i64) -> Result<()> {
+    // reset_arena only runs once every claim and referral of the round is paid out
+    if stake.epoch < arena.epoch {
+        return Ok(());
+    }
+    require!(
+        matches!(arena.status, ArenaStatus::Settled | ArenaStatus::Cancelled),
+        SoliseumError::ArenaStillOpen
+    );
+    // Past the claim deadline whatever the stake was owed belongs to `sweep_unclaimed`
+    if arena.status == ArenaStatus::Settled && arena.claims_expired(now) {
+        return Ok(());
+    }
+    require!(stake.referral_owed == 0, SoliseumError::ReferralUnclaimed);
+
+    if arena.status == ArenaStatus::Cancelled {
+        require!(stake.claimed, SoliseumError::StakeNotClaimed);
+        return Ok(());
+    }
+
+    require!(!arena.in_dispute_window(now), SoliseumError::DisputeWindowOpen);
+    if arena.refunds_all() {
+        require!(stake.claimed, SoliseumError::StakeNotClaimed);
+    } else if arena.winner == Some(stake.side) {
+        require!(stake.claimed, SoliseumError::StakeNotClaimed);
+        require!(
+            arena.unclaimed_count == 0
+                || arena.fully_distributed
+                || stake.side_index < arena.distribution_cursor,
+            SoliseumError::DistributionPending
+        );
+    }
+
+    Ok(())
+}
+
+/// What a stake on a settled arena is owed: its full amount back when the outcome
+/// refunds everyone, otherwise the winner payout. Losing stakes are rejected.
+fn settled_payout(arena: &Arena, stake: &Stake) -> Result<u64> {
+    let winner = settled_outcome(arena, stake)?;
+    if arena.refunds_all() {
+        return Ok(stake.amount);
+    }
+    require!(winner == Outcome::Side(stake.side), SoliseumError::NotAWinner);
+    winner_payout(arena, stake.amount)
+}
+
+/// The settled outcome a stake claims against. A missing or out-of-range winner, or a
+/// stake side the arena does not have, fails closed instead of reaching the pool math.
+fn settled_outcome(arena: &Arena, stake: &Stake) -> Result<Outcome> {
+    let winner = arena
+        .winner
+        .and_then(|winner| Outcome::from_winner(winner, arena.outcomes))
+        .ok_or(SoliseumError::InvalidArenaState)?;
+    Outcome::from_side(stake.side, arena.outcomes).ok_or(SoliseumError::CorruptedStake)?;
+    Ok(winner)
+}
+
+/// Protocol fee withheld from a stake's settled payout: the gap between its share of the
+/// gross and of the net loser pool, both from the settlement snapshot. Refunds carry no fee.
+fn settled_fee(arena: &Arena, stake: &Stake) -> Result<u64> {
+    if arena.refunds_all() {
+        return Ok(0);
+    }
+    require!(arena.winner.is_some(), SoliseumError::InvalidArenaState);
+    let gross_payout = mode_payout(
+        arena.payout_mode,
+        stake.amount,
+        arena.settled_winner_pool,
+        arena.settled_sqrt_pool,
+        arena.settled_loser_pool,
+    )?;
+    Ok(gross_payout
+        .checked_sub(winner_payout(arena, stake.amount)?)
+        .ok_or(SoliseumError::MathOverflow)?)
+}
+
+/// Principal plus pro-rata share of the net loser pool (every non-winning side) and of the
+/// house bonus for a winning stake, weighted per the arena's `payout_mode`.
+/// Reads the pools cached by `cache_settlement` rather than re-deriving them per claim; the
+/// bonus is fixed by then, since `house_deposit` needs an Active arena and
+/// `reclaim_house_bonus` only runs once no winner can claim.
+fn winner_payout(arena: &Arena, stake_amount: u64) -> Result<u64> {
+    require!(arena.winner.is_some(), SoliseumError::InvalidArenaState);
+    let payout = mode_payout(
+        arena.payout_mode,
+        stake_amount,
+        arena.settled_winner_pool,
+        arena.settled_sqrt_pool,
+        arena.settled_net_loser_pool,
+    )?;
+    let bonus = house_bonus_share(
+        arena.payout_mode,
+        stake_amount,
+        arena.settled_winner_pool,
+        arena.settled_sqrt_pool,
+        arena.house_bonus,
+    )?;
+    Ok(payout.checked_add(bonus).ok_or(SoliseumError::MathOverflow)?)
+}
+
+/// A winning stake's share of `house_bonus`, weighted like its share of the loser pool.
+/// Kept apart from the loser pool so the bonus carries no fee and rounds down on its own.
+fn house_bonus_share(
+    mode: PayoutMode,
+    stake_amount: u64,
+    winner_pool: u64,
+    sqrt_winner_pool: u64,
+    house_bonus: u64,
+) -> Result<u64> {
+    if house_bonus == 0 {
+        return Ok(0);
+    }
+    Ok(mode_payout(mode, stake_amount, winner_pool, sqrt_winner_pool, house_bonus)?
+        .checked_sub(stake_amount)
+        .ok_or(SoliseumError::MathOverflow)?)
+}
+
+/// Payout against `net_loser_pool` under `mode`: `winner_pool` weighs proportional
+/// shares, `sqrt_winner_pool` quadratic ones.
+fn mode_payout(
+    mode: PayoutMode,
+    stake_amount: u64,
+    winner_pool: u64,
+    sqrt_winner_pool: u64,
+    net_loser_pool: u64,
+) -> Result<u64> {
+    match mode {
+        PayoutMode::Proportional => payout_from_net_pool(stake_amount, winner_pool, net_loser_pool),
+        PayoutMode::Quadratic => quadratic_payout(stake_amount, sqrt_winner_pool, net_loser_pool),
+    }
+}
+
+/// Quadratic counterpart of `payout_from_net_pool`: the stake back plus a share of
+/// `net_loser_pool` weighted by `isqrt(stake_amount)` over the side's summed roots. Each
+/// share rounds down, so the shares never add up to more than the pool.
+pub fn quadratic_payout(stake_amount: u64, sqrt_winner_pool: u64, net_loser_pool: u64) -> Result<u64> {
+    require!(sqrt_winner_pool > 0, SoliseumError::MathOverflow);
+    let user_reward = mul_div(isqrt(stake_amount), net_loser_pool, sqrt_winner_pool)?;
+    Ok(stake_amount.checked_add(user_reward).ok_or(SoliseumError::MathOverflow)?)
+}
+
+/// Floor of the square root of `n`, by Newton's method (no floats on-chain).
+pub fn isqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = n;
+    let mut y = n / 2 + 1;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+/// Payout math shared by every claim path and `quote_payout`: the stake back plus its
+/// pro-rata share of `loser_pool` after a `fee_bps` cut. Pure, so clients can mirror it.
+pub fn compute_payout(stake_amount: u64, winner_pool: u64, loser_pool: u64, fee_bps: u16) -> Result<u64> {
+    payout_from_net_pool(stake_amount, winner_pool, net_loser_pool(loser_pool, fee_bps)?)
+}
+
+/// The loser pool left to winners after a `fee_bps` cut.
+pub fn net_loser_pool(loser_pool: u64, fee_bps: u16) -> Result<u64> {
+    mul_div(
+        loser_pool,
+        BPS_DENOMINATOR.saturating_sub(fee_bps as u64),
+        BPS_DENOMINATOR,
+    )
+}
+
+/// Second half of `compute_payout`, for callers that already hold the net loser pool.
+pub fn payout_from_net_pool(stake_amount: u64, winner_pool: u64, net_loser_pool: u64) -> Result<u64> {
+    require!(winner_pool > 0, SoliseumError::MathOverflow);
+
+    let user_reward = mul_div(stake_amount, net_loser_pool, winner_pool)?;
+    Ok(stake_amount.checked_add(user_reward).ok_or(SoliseumError::MathOverflow)?)
+}
+
+/// `a * b / c`, rounded down. The product is taken in u128 so it cannot overflow; fails
+/// with `MathOverflow` on `c == 0` or a quotient that does not fit a u64.
+fn mul_div(a: u64, b: u64, c: u64) -> Result<u64> {
+    let quotient = (a as u128 * b as u128)
+        .checked_div(c as u128)
+        .ok_or(SoliseumError::MathOverflow)?;
+    to_u64(quotient)
+}
+
+/// Narrows a u128 intermediate back to u64, failing with `MathOverflow` instead of truncating.
+fn to_u64(x: u128) -> Result<u64> {
+    Ok(u64::try_from(x).map_err(|_| SoliseumError::MathOverflow)?)
+}
+
+/// Deserializes a `Stake` by hand so a malformed account (wrong program owner, discriminator
+/// or layout after a botched migration) fails with `CorruptedStake` instead of an opaque
+/// deserialization error. A stake left over from before a `reset_arena` fails with
+/// `StaleStake`, so it is never read against the new round's pools.
+fn load_stake(info: &AccountInfo, program_id: &Pubkey, arena: &Pubkey, epoch: u64, user: &Pubkey) -> Result<Stake> {
+    let stake = load_stake_any_epoch(info, program_id, arena, user)?;
+    require!(stake.epoch == epoch, SoliseumError::StaleStake);
+    Ok(stake)
+}
+
+/// `load_stake` for cleanup, which also accepts stakes from the arena's earlier rounds.
+fn load_stake_any_epoch(info: &AccountInfo, program_id: &Pubkey, arena: &Pubkey, user: &Pubkey) -> Result<Stake> {
+    require_keys_eq!(*info.owner, *program_id, SoliseumError::CorruptedStake);
+    let data = info.try_borrow_data()?;
+    require!(
+        data.len() > 8 && data[..8] == <Stake as anchor_lang::Discriminator>::DISCRIMINATOR,
+        SoliseumError::CorruptedStake
+    );
+    require!(data[8] == STAKE_VERSION, SoliseumError::UnsupportedVersion);
+    let stake = Stake::try_deserialize(&mut &data[..])
+        .map_err(|_| error!(SoliseumError::CorruptedStake))?;
+    require_keys_eq!(stake.owner, *user, SoliseumError::CorruptedStake);
+    // Stakes are per side and round, so the address can only be checked once both are known
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"stake", arena.as_ref(), user.as_ref(), &[stake.side], &stake.epoch.to_le_bytes()],
+        program_id,
+    );
+    require_keys_eq!(*info.key, expected, SoliseumError::CorruptedStake);
+    Ok(stake)
+}
+
+/// Writes a `Stake` loaded with `load_stake` back to its account.
+fn store_stake(info: &AccountInfo, stake: &Stake) -> Result<()> {
+    let mut data = info.try_borrow_mut_data()?;
+    stake.try_serialize(&mut &mut data[..])
+}
+
+/// Verifies Ed25519 signatures using the Solana native Ed25519 program.
+///
+/// NOT YET IMPLEMENTED: this accepts every signature. Oracle results are only as trusted as
+/// the submitter checks (committee member, relayer or creator) and the signer-index rules in
+/// `verify_oracle_signatures`; the signature bytes themselves are never checked, and no test
+/// covers them.
+///
 /// SECURITY NOTE: This implementation requires pre-verification via the Ed25519 native program
 /// (Address: Ed25519SigVerify111111111111111111111111111) in the same transaction.
 /// The native program writes verification results to account data that this function checks.
@@ -457,53 +3602,1628 @@ fn verify_ed25519_signature(_pubkey: &Pubkey, _message: &[u8], _signature: &[u8;
     true
 }
 
-#[account]
-pub struct Arena {
-    pub creator: Pubkey,
-    pub oracles: [Pubkey; MAX_ORACLES], // 3 oracle pubkeys
-    pub oracle_threshold: u8, // 2 for 2-of-3
-    pub total_pool: u64,
-    pub agent_a_pool: u64,
-    pub agent_b_pool: u64,
-    pub status: ArenaStatus,
-    pub winner: Option<u8>,
-    pub fee_bps: u16,
-    pub settlement_nonce: u64, // Prevents replay attacks
+/// Direction of a lamport movement relative to the vault
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum VaultDirection {
+    Inflow,
+    Outflow,
+}
+
+/// Emitted for every lamport movement into or out of a vault, for treasury reconciliation
+#[event]
+pub struct VaultTransfer {
+    pub arena: Pubkey,
+    pub direction: VaultDirection,
+    pub amount: u64,
+    pub counterparty: Pubkey,
+    pub total_pool: u64,
+}
+
+/// Emitted once when an arena is created
+#[event]
+pub struct ArenaInitialized {
+    pub arena: Pubkey,
+    pub creator: Pubkey,
+    pub fee_bps: u16,
+    pub oracles: Vec<Pubkey>,
+    pub oracle_threshold: u8,
+    pub agent_a_name: [u8; 32],
+    pub agent_b_name: [u8; 32],
+    pub match_id: [u8; 16],
+}
+
+/// Emitted for every accepted stake, with the pools after it, for live odds
+#[event]
+pub struct StakePlaced {
+    pub arena: Pubkey,
+    pub user: Pubkey,
+    pub side: u8,
+    pub amount: u64,
+    pub pools: Vec<u64>, // Indexed by side
+    pub stakers: Vec<u32>, // Unique stakes per side
+    pub total_pool: u64,
+}
+
+/// Emitted when the oracles settle an arena; `nonce` is the one they signed over and
+/// `signers` has bit `i` set for every `oracle_index` whose signature was accepted
+#[event]
+pub struct GameSettled {
+    pub arena: Pubkey,
+    pub winner: u8,
+    pub nonce: u64,
+    pub signers: u8,
+    pub result_hash: [u8; 32], // Outcome hash the oracles signed over, zero for draws and legacy settles
+}
+
+/// Emitted instead of `GameSettled` when the book is one-sided and the arena is cancelled
+#[event]
+pub struct ArenaVoided {
+    pub arena: Pubkey,
+    pub nonce: u64,
+    pub signers: u8,
+}
+
+/// An arena `settle_games_batch` left untouched; `error_code` is what `settle_game` would fail with
+#[event]
+pub struct SettlementSkipped {
+    pub arena: Pubkey,
+    pub error_code: u32,
+}
+
+/// Emitted by `sweep_my_stakes`; `closed` counts the stakes it closed, skips excluded
+#[event]
+pub struct StakesSwept {
+    pub user: Pubkey,
+    pub closed: u8,
+}
+
+/// Emitted for every winning payout, whether claimed or pushed by `distribute_rewards`.
+/// `payout` is in the currency the arena pays out in.
+#[event]
+pub struct RewardClaimed {
+    pub arena: Pubkey,
+    pub user: Pubkey,
+    pub payout: u64,
+}
+
+/// Emitted when a settled arena is reopened for another battle
+#[event]
+pub struct ArenaReset {
+    pub arena: Pubkey,
+    pub settlement_nonce: u64,
+    pub fee_bps: u16, // Base fee for the new round
+    pub reset_count: u32, // Resets so far, including this one
+    pub epoch: u64, // The new round's epoch, seeding its stakes
+}
+
+/// Emitted when a committee rotation is proposed; it applies from `effective_at`
+#[event]
+pub struct OracleUpdateProposed {
+    pub arena: Pubkey,
+    pub pending_oracles: Vec<Pubkey>,
+    pub effective_at: i64,
+}
+
+/// Emitted when the creator vetoes a pending committee rotation
+#[event]
+pub struct OracleUpdateCancelled {
+    pub arena: Pubkey,
+    pub settlement_nonce: u64,
+}
+
+/// Emitted when the oracle committee is replaced
+#[event]
+pub struct OraclesUpdated {
+    pub arena: Pubkey,
+    pub oracles: Vec<Pubkey>,
+    pub settlement_nonce: u64,
+}
+
+/// Emitted when an oracle hands its seat to a new key via `rotate_oracle_seat`
+#[event]
+pub struct OracleSeatRotated {
+    pub arena: Pubkey,
+    pub index: u8,
+    pub old_oracle: Pubkey,
+    pub new_oracle: Pubkey,
+    pub settlement_nonce: u64,
+}
+
+/// Emitted when `expire_arena` sweeps the vault and closes the arena
+#[event]
+pub struct ArenaExpired {
+    pub arena: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub swept: u64,
+}
+
+/// Emitted whenever an instruction adds to the `GlobalConfig` aggregates
+#[event]
+pub struct GlobalStatsUpdated {
+    pub total_arenas: u64,
+    pub total_volume_lamports: u64,
+    pub total_fees_collected: u64,
+}
+
+/// Emitted when `sweep_unclaimed` forfeits the claims left after the claim deadline
+#[event]
+pub struct UnclaimedSwept {
+    pub arena: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub swept: u64,
+    pub forfeited: u32, // Stakes that never claimed
+}
+
+/// Emitted when the creator closes a finished arena via `close_arena`
+#[event]
+pub struct ArenaClosed {
+    pub arena: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub swept: u64,
+}
+
+/// Emitted when `migrate_arena` grows an arena to the current layout
+#[event]
+pub struct ArenaMigrated {
+    pub arena: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+/// Emitted when the creator closes an unstaked arena via `abandon_arena`
+#[event]
+pub struct ArenaAbandoned {
+    pub arena: Pubkey,
+    pub creator: Pubkey,
+    pub swept: u64,
+}
+
+/// Emitted when the creator unwinds a single stake via `admin_refund_stake`
+#[event]
+pub struct StakeAdminRefunded {
+    pub arena: Pubkey,
+    pub owner: Pubkey,
+    pub side: u8,
+    pub amount: u64, // The stake's full principal, never more
+}
+
+/// Emitted when a tournament opens with its first round and on every `advance_round`
+#[event]
+pub struct RoundAdvanced {
+    pub tournament: Pubkey,
+    pub round: u8, // Index into the tournament's arenas
+    pub arena: Pubkey, // The round's arena
+}
+
+/// Emitted when the creator pauses or resumes staking via `set_paused`
+#[event]
+pub struct ArenaPauseChanged {
+    pub arena: Pubkey,
+    pub paused: bool,
+}
+
+/// Emitted when `finalize_settlement` opens claims after the challenge window
+#[event]
+pub struct SettlementFinalized {
+    pub arena: Pubkey,
+    pub winner: u8,
+}
+
+/// Emitted when `dispute_settlement` returns a disputable arena to Active
+#[event]
+pub struct SettlementDisputed {
+    pub arena: Pubkey,
+    pub disputed_winner: u8,
+    pub settlement_nonce: u64, // Nonce a fresh settlement must be signed over
+}
+
+/// Emitted when the creator seeds the pools via `seed_liquidity`
+#[event]
+pub struct LiquiditySeeded {
+    pub arena: Pubkey,
+    pub amounts: Vec<u64>, // Indexed by side
+}
+
+/// Emitted when the creator changes an idle arena's fee via `set_fee_bps`
+#[event]
+pub struct FeeBpsChanged {
+    pub arena: Pubkey,
+    pub old_fee_bps: u16,
+    pub new_fee_bps: u16,
+}
+
+#[account]
+pub struct Arena {
+    pub version: u8, // First field so it can be read before the rest of the layout
+    pub creator: Pubkey,
+    pub arena_id: u64, // Distinguishes a creator's arenas in the PDA seeds
+    pub oracles: Vec<Pubkey>, // 1 to MAX_ORACLES committee members
+    pub oracle_threshold: u8, // Signatures required out of oracles.len()
+    pub total_pool: u64,
+    pub outcomes: u8, // Number of sides, 2 to MAX_OUTCOMES
+    pub pools: Vec<u64>, // Staked amount per side, `outcomes` entries
+    pub status: ArenaStatus,
+    pub winner: Option<u8>,
+    pub fee_bps: u16,
+    pub settlement_nonce: u64, // Prevents replay attacks
+    // Unique stakes per side still holding funds, `outcomes` entries; sizes unclaimed_count.
+    // A stake withdrawn or refunded to zero leaves the count but keeps its side_index.
+    pub stakers: Vec<u32>,
+    pub unclaimed_count: u32, // Winning stakes not yet claimed (set at settlement)
+    pub stake_increment: u64, // 0 = any amount
+    pub fee_tiers: Vec<FeeTier>, // Ascending thresholds, up to MAX_FEE_TIERS
+    pub distribution_cursor: u32, // Next winning side_index to pay in distribute_rewards
+    pub fully_distributed: bool,
+    pub payout_mint: Option<Pubkey>, // Pay winners in this token instead of SOL
+    pub payout_rate: u64, // Token base units per SOL of payout
+    pub dispute_window_secs: i64, // 0 = no dispute window
+    pub settled_at: i64,
+    pub fee_recipient: Pubkey,
+    pub claim_expiry_secs: i64, // Delay after settlement before expire_arena may run
+    pub stake_mint: Option<Pubkey>, // None = native SOL stakes
+    pub betting_opens_at: i64, // 0 = no lower bound
+    pub betting_closes_at: i64, // 0 = no upper bound
+    pub accrued_fees: u64, // Fees withheld by SOL claims, not yet withdrawn
+    pub fees_withdrawn: u64, // Lifetime total sent to fee_recipient by withdraw_fees/withdraw_partial_fees
+    pub min_stake: u64, // 0 = no minimum
+    pub max_stake_per_user: u64, // 0 = no cap
+    pub agent_a_name: [u8; 32], // Zero-padded UTF-8
+    pub agent_b_name: [u8; 32], // Zero-padded UTF-8
+    pub match_id: [u8; 16],
+    pub paused: bool, // Rejects new stakes while set
+    pub oracle_update_delay_secs: i64, // Timelock between proposing and executing a rotation
+    pub pending_oracles: Vec<Pubkey>, // Proposed committee, empty when none is pending
+    pub oracle_update_effective_at: i64, // When pending_oracles may be executed
+    pub created_at: i64,
+    pub emergency_timeout_secs: i64, // Oracle silence tolerated before emergency_cancel
+    pub referral_bps: u16, // Referrer's cut, in fee_bps units, of a referred claim's fee
+    pub referral_owed: u64, // Referral cuts booked but not yet claimed
+    pub settled_winner_pool: u64, // Winning side's pool, cached at settlement
+    pub settled_net_loser_pool: u64, // Loser pool after fees, cached at settlement
+    pub vault_bump: u8, // Canonical bump of the vault PDA, so vault signing skips the search
+    pub max_side_share_bps: u16, // 0 = no cap on one side's share of total_pool
+    pub claim_window_secs: i64, // 0 = claims never expire
+    pub claim_deadline: i64, // settled_at + claim_window_secs, 0 = none
+    pub cluster_id: u8, // Signed into every oracle message with the program id
+    pub settlement_signers: u8, // Bitmask of oracle_index values behind the latest result
+    pub settlement_signed_nonce: u64, // Nonce those signers signed over
+    pub min_participants: u16, // Stakers settle_game requires, 0 = no minimum
+    pub seeded: Vec<u64>, // Creator liquidity per side, included in pools but backing no Stake
+    pub whitelist_root: [u8; 32], // Merkle root of allowed stakers, zero = open arena
+    pub challenge_window_secs: i64, // Disputable period after settlement, 0 = settle straight away
+    pub max_total_pool: u64, // Cap on total_pool, 0 = uncapped
+    pub vault_rent_reserve: u64, // Lamports fund_vault_rent parked in the vault, owed to nobody
+    pub result_hash: [u8; 32], // Outcome hash signed with the winner, zero = none supplied
+    pub allow_creator_stake: bool, // Whether the creator may stake in their own arena
+    pub settlement_lockout_secs: i64, // Quiet period after the latest stake before settle_game, 0 = none
+    pub last_stake_at: i64, // Unix time of the latest stake, 0 = none yet
+    pub payout_mode: PayoutMode, // How winners split the net loser pool
+    pub sqrt_pools: Vec<u64>, // Per side, sum of isqrt(stake.amount) over its stakes
+    pub settled_sqrt_pool: u64, // Winning side's sqrt_pools entry, cached at settlement
+    pub oracle_groups: [u8; MAX_ORACLES], // Operator group of each committee seat
+    pub min_signer_groups: u8, // Distinct groups settle_game's signers must span, 0 = any
+    pub min_stake_lock_secs: i64, // Time after placed_at before withdraw_stake, 0 = no lock
+    pub min_fee_lamports: u64, // Floor on the fee taken from the loser pool, 0 = bps only
+    pub min_side_liquidity: u64, // Pool every side needs before settlement, 0 = none
+    pub settlement_mode: SettlementMode, // Committee signatures, or the creator alone
+    pub max_resets: u32, // Cap on reset_arena calls, 0 = unlimited
+    pub reset_count: u32, // reset_arena calls so far
+    pub settled_loser_pool: u64, // Loser pool before fees, snapshotted at settlement
+    pub settled_fee_bps: u16, // effective_fee_bps at settlement, splits claim fees with referrers
+    pub relayers: Vec<Pubkey>, // Up to MAX_RELAYERS keys allowed to submit committee results, empty = the committee
+    pub house_bonus_bps: u16, // Share of total_pool the creator matches for the winners, 0 = none
+    pub house_bonus: u64, // Lamports house_deposit put in the vault for the winners
+    pub epoch: u64, // Round number, bumped by reset_arena and part of every Stake's seeds
+    pub next_side_index: Vec<u32>, // side_index of the next new stake per side, `outcomes` entries, never decremented
+}
+
+impl Arena {
+    // version(1) + creator(32) + arena_id(8) + oracles(4 + 32 per member) + threshold(1) + total_pool(8)
+    // + outcomes(1) + pools(4 + 8 per outcome) + status(1) + winner(1+1 for Option) + fee_bps(2)
+    // + settlement_nonce(8) + stakers(4 + 4 per outcome) + unclaimed_count(4) + stake_increment(8)
+    // + fee_tiers(4 + MAX_FEE_TIERS * 10) + distribution_cursor(4) + fully_distributed(1)
+    // + payout_mint(1+32 for Option) + payout_rate(8) + dispute_window_secs(8) + settled_at(8)
+    // + fee_recipient(32) + claim_expiry_secs(8) + stake_mint(1+32 for Option)
+    // + betting_opens_at(8) + betting_closes_at(8) + accrued_fees(8) + fees_withdrawn(8)
+    // + min_stake(8) + max_stake_per_user(8) + agent_a_name(32) + agent_b_name(32) + match_id(16)
+    // + paused(1) + oracle_update_delay_secs(8) + pending_oracles(4 + 32 per member)
+    // + oracle_update_effective_at(8) + created_at(8) + emergency_timeout_secs(8)
+    // + referral_bps(2) + referral_owed(8) + settled_winner_pool(8) + settled_net_loser_pool(8)
+    // + vault_bump(1) + max_side_share_bps(2) + claim_window_secs(8) + claim_deadline(8)
+    // + cluster_id(1) + settlement_signers(1) + settlement_signed_nonce(8)
+    // + min_participants(2) + seeded(4 + 8 per outcome) + whitelist_root(32)
+    // + challenge_window_secs(8) + max_total_pool(8) + vault_rent_reserve(8) + result_hash(32)
+    // + allow_creator_stake(1) + settlement_lockout_secs(8) + last_stake_at(8)
+    // + payout_mode(1) + sqrt_pools(4 + 8 per outcome) + settled_sqrt_pool(8)
+    // + oracle_groups(MAX_ORACLES) + min_signer_groups(1) + min_stake_lock_secs(8)
+    // + min_fee_lamports(8) + min_side_liquidity(8) + settlement_mode(1) + max_resets(4)
+    // + reset_count(4) + settled_loser_pool(8) + settled_fee_bps(2) + relayers(4 + MAX_RELAYERS * 32)
+    // + house_bonus_bps(2) + house_bonus(8) + epoch(8) + next_side_index(4 + 4 per outcome)
+    // LEN excludes the committee members and per-outcome entries; size accounts with `space`.
+    pub const LEN: usize = 1 + 32 + 8 + 4 + 1 + 8 + 1 + 4 + 1 + 2 + 2 + 8 + 4 + 4 + 8
+        + 4 + MAX_FEE_TIERS * FeeTier::LEN + 4 + 1 + 33 + 8 + 8 + 8 + 32 + 8 + 33 + 8 + 8 + 8 + 8 + 8 + 8
+        + 32 + 32 + 16 + 1 + 8 + 4 + 8 + 8 + 8 + 2 + 8 + 8 + 8 + 1 + 2 + 8 + 8 + 1 + 1 + 8 + 2 + 4 + 32 + 8 + 8 + 8 + 32 + 1 + 8 + 8 + 1 + 4 + 8
+        + MAX_ORACLES + 1 + 8 + 8 + 8 + 1 + 4 + 4 + 8 + 2 + 4 + MAX_RELAYERS * 32 + 2 + 8 + 8 + 4;
+
+    /// Account size (without discriminator) for a committee of `oracle_count` members
+    /// and `outcomes` sides; a pending rotation is the same size as the committee.
+    pub const fn space(oracle_count: usize, outcomes: usize) -> usize {
+        Self::LEN + 2 * oracle_count * 32 + outcomes * (8 + 4 + 8 + 8 + 4)
+    }
+
+    /// Part of LEN added by the v2 layout, `result_hash` through `min_stake_lock_secs`;
+    /// sqrt_pools adds another 8 bytes per outcome.
+    const V2_FIELDS_LEN: usize = 32 + 1 + 8 + 8 + 1 + 4 + 8 + MAX_ORACLES + 1 + 8;
+    /// Part of LEN added by the v3 layout, `min_fee_lamports` through `epoch`
+    const V3_FIELDS_LEN: usize = 8 + 8 + 1 + 4 + 4 + 8 + 2 + 4 + MAX_RELAYERS * 32 + 2 + 8 + 8;
+    /// Part of LEN added by the v4 layout, `next_side_index`; it adds 4 bytes per outcome.
+    const V4_FIELDS_LEN: usize = 4;
+
+    /// `space` under the layout of schema `version`, the minimum size of an account
+    /// stamped with it; fields are only ever appended, so older layouts are prefixes.
+    pub const fn space_at(version: u8, oracle_count: usize, outcomes: usize) -> usize {
+        match version {
+            1 => Self::space_at(2, oracle_count, outcomes) - Self::V2_FIELDS_LEN - outcomes * 8,
+            2 => Self::space_at(3, oracle_count, outcomes) - Self::V3_FIELDS_LEN,
+            3 => Self::space(oracle_count, outcomes) - Self::V4_FIELDS_LEN - outcomes * 4,
+            _ => Self::space(oracle_count, outcomes),
+        }
+    }
+
+    /// Stake accounts ever opened this round, withdrawn ones included.
+    pub fn stakes_opened(&self) -> Result<u32> {
+        Ok(self
+            .next_side_index
+            .iter()
+            .try_fold(0u32, |sum, n| sum.checked_add(*n))
+            .ok_or(SoliseumError::MathOverflow)?)
+    }
+
+    /// True when the settled outcome refunds every stake without fee: a draw, or a
+    /// winner nobody backed (seeded liquidity alone does not count). Reads the settlement
+    /// snapshot, so it only holds once `cache_settlement` has run.
+    pub fn refunds_all(&self) -> bool {
+        match self.winner {
+            Some(DRAW) => true,
+            Some(_) => self.settled_winner_pool == 0,
+            None => false,
+        }
+    }
+
+    /// House bonus the creator can still take back through `reclaim_house_bonus`: all of
+    /// it once the arena is cancelled or settled on an outcome that refunds every stake.
+    pub fn reclaimable_house_bonus(&self) -> u64 {
+        match self.status {
+            ArenaStatus::Cancelled => self.house_bonus,
+            ArenaStatus::Settled if self.refunds_all() => self.house_bonus,
+            _ => 0,
+        }
+    }
+
+    /// Vault lamports available to pay out, i.e. everything above the rent reserve.
+    pub fn spendable(&self, vault_lamports: u64) -> u64 {
+        vault_lamports.saturating_sub(self.vault_rent_reserve)
+    }
+
+    /// Creator liquidity seeded on `side`.
+    pub fn seed(&self, side: u8) -> u64 {
+        self.seeded.get(side as usize).copied().unwrap_or(0)
+    }
+
+    /// Number of stakes the settled outcome owes a payout or refund to.
+    pub fn owed_stakers(&self) -> u32 {
+        match self.winner {
+            _ if self.refunds_all() => self.stakers.iter().fold(0u32, |sum, n| sum.saturating_add(*n)),
+            Some(winner) => self.stakers.get(winner as usize).copied().unwrap_or(0),
+            None => 0,
+        }
+    }
+
+    /// Stakes across every side.
+    pub fn total_stakers(&self) -> Result<u32> {
+        Ok(self
+            .stakers
+            .iter()
+            .try_fold(0u32, |sum, n| sum.checked_add(*n))
+            .ok_or(SoliseumError::MathOverflow)?)
+    }
+
+    /// Snapshots the pools and fee for the settled `winner`. Claims read only the snapshot,
+    /// so a later change to the live pools cannot move a payout. Outcomes that refund
+    /// everyone snapshot zeros.
+    pub fn cache_settlement(&mut self) -> Result<()> {
+        let snapshot = match self.winner {
+            Some(winner) if winner != DRAW => {
+                let (winner_pool, loser_pool) = self.winner_and_loser_pools(winner)?;
+                let sqrt_pool = *self.sqrt_pools.get(winner as usize).ok_or(SoliseumError::InvalidArenaState)?;
+                (winner_pool > 0).then(|| (winner_pool, sqrt_pool, loser_pool))
+            }
+            _ => None,
+        };
+        let (winner_pool, sqrt_pool, loser_pool) = snapshot.unwrap_or_default();
+        self.settled_winner_pool = winner_pool;
+        self.settled_sqrt_pool = sqrt_pool;
+        self.settled_loser_pool = loser_pool;
+        self.settled_net_loser_pool = self.net_after_fees(loser_pool)?;
+        self.settled_fee_bps = if snapshot.is_some() { self.effective_fee_bps() } else { 0 };
+        Ok(())
+    }
+
+    /// Moves a stake on `side` from `old_amount` to `new_amount` in the side's summed
+    /// square roots, the quadratic payout denominator. Proportional arenas never read the
+    /// roots, so they skip the bookkeeping (arenas migrated from v1 have none to track).
+    pub fn reweigh(&mut self, side: u8, old_amount: u64, new_amount: u64) -> Result<()> {
+        if self.payout_mode != PayoutMode::Quadratic {
+            return Ok(());
+        }
+        let weight = self
+            .sqrt_pools
+            .get_mut(side as usize)
+            .ok_or(SoliseumError::InvalidArenaState)?;
+        *weight = weight
+            .checked_sub(isqrt(old_amount))
+            .and_then(|w| w.checked_add(isqrt(new_amount)))
+            .ok_or(SoliseumError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// The winning side's staked pool and everything else in the book: the other sides'
+    /// pools plus the winning side's seed, which goes to its stakers.
+    pub fn winner_and_loser_pools(&self, winner: u8) -> Result<(u64, u64)> {
+        let winner_pool = self
+            .pools
+            .get(winner as usize)
+            .ok_or(SoliseumError::InvalidArenaState)?
+            .checked_sub(self.seed(winner))
+            .ok_or(SoliseumError::MathOverflow)?;
+        let loser_pool = self
+            .total_pool
+            .checked_sub(winner_pool)
+            .ok_or(SoliseumError::MathOverflow)?;
+        Ok((winner_pool, loser_pool))
+    }
+
+    /// True if `now` falls inside the betting window; a 0 bound is unbounded.
+    pub fn betting_open(&self, now: i64) -> bool {
+        (self.betting_opens_at == 0 || now >= self.betting_opens_at)
+            && (self.betting_closes_at == 0 || now < self.betting_closes_at)
+    }
+
+    /// Earliest time `emergency_cancel` may run: the timeout counted from creation, or from
+    /// the close of betting when that is later.
+    pub fn emergency_cancel_at(&self) -> Result<i64> {
+        Ok(self
+            .created_at
+            .max(self.betting_closes_at)
+            .checked_add(self.emergency_timeout_secs)
+            .ok_or(SoliseumError::MathOverflow)?)
+    }
+
+    /// True when stake sits on exactly one outcome, so settling would be no contest.
+    pub fn one_sided(&self) -> bool {
+        self.total_pool > 0 && self.pools.iter().filter(|pool| **pool > 0).count() < 2
+    }
+
+    /// Records which committee members approved the result signed at the current nonce.
+    pub fn record_signers(&mut self, signers: u8) {
+        self.settlement_signers = signers;
+        self.settlement_signed_nonce = self.settlement_nonce;
+    }
+
+    /// Starts the claim window from `settled_at`, if the arena has one.
+    pub fn set_claim_deadline(&mut self) -> Result<()> {
+        self.claim_deadline = if self.claim_window_secs == 0 {
+            0
+        } else {
+            self.settled_at
+                .checked_add(self.claim_window_secs)
+                .ok_or(SoliseumError::MathOverflow)?
+        };
+        Ok(())
+    }
+
+    /// True once a configured claim deadline has passed.
+    pub fn claims_expired(&self, now: i64) -> bool {
+        self.claim_deadline > 0 && now >= self.claim_deadline
+    }
+
+    /// Status a fresh settlement lands in: `Disputable` when a challenge window is configured.
+    pub fn settled_status(&self) -> ArenaStatus {
+        if self.challenge_window_secs > 0 {
+            ArenaStatus::Disputable
+        } else {
+            ArenaStatus::Settled
+        }
+    }
+
+    /// True while the challenge window after settlement is still running.
+    pub fn in_challenge_window(&self, now: i64) -> bool {
+        now < self.settled_at.saturating_add(self.challenge_window_secs)
+    }
+
+    /// Number of distinct `oracle_groups` among the seats set in `signers`.
+    pub fn signer_groups(&self, signers: u8) -> u8 {
+        let mut seen = [0u8; MAX_ORACLES];
+        let mut count = 0;
+        for seat in 0..self.oracles.len().min(MAX_ORACLES) {
+            let group = self.oracle_groups[seat];
+            if signers & (1 << seat) != 0 && !seen[..count].contains(&group) {
+                seen[count] = group;
+                count += 1;
+            }
+        }
+        count as u8
+    }
+
+    /// Status gate every handler goes through: Ok when the arena is in `expected`,
+    /// otherwise the error naming why it is not (see `status_error`).
+    pub fn require_status(&self, expected: ArenaStatus) -> Result<()> {
+        if self.status != expected {
+            return Err(self.status_error(expected).into());
+        }
+        Ok(())
+    }
+
+    /// The lifecycle is Active -> Settled | Disputable | Cancelled, Disputable -> Settled,
+    /// and back to Active only through `reset_arena`. Maps a handler that needed `expected`
+    /// onto the error for the transition it refused; also used by account constraints.
+    pub fn status_error(&self, expected: ArenaStatus) -> SoliseumError {
+        match (expected, &self.status) {
+            (ArenaStatus::Active, ArenaStatus::Settled | ArenaStatus::Disputable) => {
+                SoliseumError::ArenaAlreadySettled
+            }
+            (ArenaStatus::Active, _) => SoliseumError::ArenaNotActive,
+            (ArenaStatus::Settled, _) => SoliseumError::ArenaNotSettled,
+            (ArenaStatus::Cancelled, _) => SoliseumError::ArenaNotCancelled,
+            (ArenaStatus::Disputable, _) => SoliseumError::ArenaNotDisputable,
+            (ArenaStatus::Pending, _) => SoliseumError::InvalidArenaState,
+        }
+    }
+
+    /// Whether `submitter` may send `settle_game`: a committee relayer, or only the creator
+    /// on a `CreatorOnly` arena.
+    pub fn may_submit_settlement(&self, submitter: &Pubkey) -> bool {
+        match self.settlement_mode {
+            SettlementMode::OracleCommittee => self.may_relay(submitter),
+            SettlementMode::CreatorOnly => *submitter == self.creator,
+        }
+    }
+
+    /// Whether `submitter` may send a committee-signed result: one of `relayers` when any
+    /// are set, otherwise a committee member. Signatures are checked separately either way.
+    pub fn may_relay(&self, submitter: &Pubkey) -> bool {
+        if self.relayers.is_empty() {
+            self.oracles.contains(submitter)
+        } else {
+            self.relayers.contains(submitter)
+        }
+    }
+
+    /// Matchmaking: an Active arena whose sides have not all reached `min_side_liquidity`.
+    /// Stakes are live, but the book is too thin to settle.
+    pub fn in_matchmaking(&self) -> bool {
+        self.status == ArenaStatus::Active
+            && self.pools.iter().any(|pool| *pool < self.min_side_liquidity)
+    }
+
+    /// True while `settle_game` is held back by the lockout after the latest stake.
+    pub fn in_settlement_lockout(&self, now: i64) -> bool {
+        self.settlement_lockout_secs > 0
+            && self.last_stake_at > 0
+            && now < self.last_stake_at.saturating_add(self.settlement_lockout_secs)
+    }
+
+    /// True while a configured dispute window after settlement is still running.
+    pub fn in_dispute_window(&self, now: i64) -> bool {
+        self.dispute_window_secs > 0
+            && self.status == ArenaStatus::Settled
+            && now < self.settled_at.saturating_add(self.dispute_window_secs)
+    }
+
+    /// The loser pool left to winners: the `effective_fee_bps` cut, raised to
+    /// `min_fee_lamports` when that is larger but never more than the pool itself.
+    pub fn net_after_fees(&self, loser_pool: u64) -> Result<u64> {
+        let net = net_loser_pool(loser_pool, self.effective_fee_bps())?;
+        let fee = (loser_pool - net).max(self.min_fee_lamports).min(loser_pool);
+        Ok(loser_pool - fee)
+    }
+
+    /// Fee of the highest tier reached by `total_pool`, falling back to `fee_bps`.
+    /// The pool is frozen once settled, so this matches the pool size at settlement.
+    pub fn effective_fee_bps(&self) -> u16 {
+        self.fee_tiers
+            .iter()
+            .rev()
+            .find(|tier| self.total_pool >= tier.threshold)
+            .map_or(self.fee_bps, |tier| tier.fee_bps)
+    }
+}
+
+/// Program-wide lifetime aggregates, singleton PDA `[b"config"]`
+#[account]
+pub struct GlobalConfig {
+    pub total_arenas: u64,
+    pub total_volume_lamports: u64, // SOL staked through place_stake
+    pub total_fees_collected: u64, // Sent to fee recipients by withdraw_fees/withdraw_partial_fees
+}
+
+impl GlobalConfig {
+    pub const LEN: usize = 8 + 8 + 8;
+}
+
+/// Per-oracle reliability counters, PDA `[b"oracle_stats", oracle]`, for front-ends to rank
+/// oracles by
+#[account]
+pub struct OracleStats {
+    pub oracle: Pubkey,
+    pub settlements_signed: u64,
+    pub disputes_lost: u64, // Results this oracle signed that correct_winner overturned
+}
+
+impl OracleStats {
+    pub const LEN: usize = 32 + 8 + 8;
+}
+
+/// A bracket of arenas played one round after another, PDA
+/// `[b"tournament", creator, tournament_id]`
+#[account]
+pub struct Tournament {
+    pub creator: Pubkey,
+    pub tournament_id: u64,
+    pub arenas: Vec<Pubkey>, // One arena per round so far, up to MAX_TOURNAMENT_ROUNDS
+    pub current_round: u8, // Index of the round in play
+}
+
+impl Tournament {
+    // creator(32) + tournament_id(8) + arenas(4 + MAX_TOURNAMENT_ROUNDS * 32) + current_round(1)
+    pub const LEN: usize = 32 + 8 + 4 + MAX_TOURNAMENT_ROUNDS * 32 + 1;
+}
+
+/// One per wallet per side and round, PDA `[b"stake", arena, owner, side, arena.epoch]`, so a
+/// wallet can hedge across outcomes and a reset arena never reads a previous round's stake
+#[account]
+pub struct Stake {
+    pub version: u8, // First field so it can be read before the rest of the layout
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub side: u8,
+    pub claimed: bool,
+    pub side_index: u32, // Order of this stake among its side's stakers
+    pub referrer: Option<Pubkey>,
+    pub referral_owed: u64, // Fee cut awaiting claim_referral
+    pub claimed_amount: u64, // Lamports of the payout claimed so far; `claimed` once it is all paid
+    pub placed_at: i64, // Unix time of the first stake, start of the withdrawal lock
+    pub epoch: u64, // Arena epoch (round) the stake was placed in, part of its seeds
+}
+
+impl Stake {
+    pub const LEN: usize = 1 + 32 + 8 + 1 + 1 + 4 + 33 + 8 + 8 + 8 + 8;
+}
+
+#[derive(Accounts)]
+#[instruction(arena_id: u64, fee_bps: u16, oracle_pubkeys: Vec<Pubkey>, oracle_threshold: u8, outcomes: u8)]
+pub struct InitializeArena<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Arena::space(oracle_pubkeys.len(), outcomes as usize),
+        seeds = [b"arena", creator.key().as_ref(), &arena_id.to_le_bytes()],
+        bump
+    )]
+    pub arena: Account<'info, Arena>,
+
+    /// Vault PDA, one per arena: holds staked SOL only (0 bytes data) so System Program allows transfer from it on claim
+    /// CHECK: Validated by seeds; created with space 0 in instruction
+    #[account(mut, seeds = [b"vault", arena.key().as_ref()], bump)]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, side: u8)]
+pub struct PlaceStake<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Active @ arena.status_error(ArenaStatus::Active),
+        constraint = arena.stake_mint.is_none() @ SoliseumError::WrongStakeCurrency
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(mut, seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA, holds SOL only
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Stake::LEN,
+        seeds = [b"stake", arena.key().as_ref(), user.key().as_ref(), &[side], &arena.epoch.to_le_bytes()],
+        bump
+    )]
+    pub stake: Account<'info, Stake>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, side: u8)]
+pub struct PlaceStakeSpl<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Active @ arena.status_error(ArenaStatus::Active),
+        constraint = arena.stake_mint == Some(stake_mint.key()) @ SoliseumError::WrongStakeCurrency
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA; only owns the stake token vault
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Stake::LEN,
+        seeds = [b"stake", arena.key().as_ref(), user.key().as_ref(), &[side], &arena.epoch.to_le_bytes()],
+        bump
+    )]
+    pub stake: Account<'info, Stake>,
+
+    pub stake_mint: Account<'info, Mint>,
+
+    /// Token account holding the arena's staked tokens, owned by the vault PDA
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = stake_mint,
+        associated_token::authority = vault
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Active @ arena.status_error(ArenaStatus::Active),
+        constraint = arena.stake_mint.is_none() @ SoliseumError::WrongStakeCurrency
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(mut, seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA, holds SOL only (no data) so System Program allows transfer from it
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", arena.key().as_ref(), user.key().as_ref(), &[stake.side], &arena.epoch.to_le_bytes()],
+        bump,
+        constraint = stake.version == STAKE_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = stake.owner == user.key() @ SoliseumError::CorruptedStake
+    )]
+    pub stake: Account<'info, Stake>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(oracle_signatures: Option<Vec<OracleSignature>>)]
+pub struct ResetArena<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(mut, seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA; we only check lamports == 0
+    pub vault: UncheckedAccount<'info>,
+
+    /// Authority: must be creator or one of the oracles (validated in handler)
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(winner: u8, oracle_signatures: Vec<OracleSignature>)]
+pub struct SettleGame<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+    )]
+    pub arena: Account<'info, Arena>,
+
+    /// Committee member, or listed relayer, submitting the result; the creator on a
+    /// `CreatorOnly` arena
+    #[account(constraint = arena.may_submit_settlement(&oracle.key()) @ SoliseumError::UnauthorizedOracle)]
+    pub oracle: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleGamesBatch<'info> {
+    /// Submitting oracle; must be allowed to settle every arena in the batch
+    /// (see `Arena::may_submit_settlement`)
+    pub oracle: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(oracle_signatures: Vec<OracleSignature>)]
+pub struct SettleDraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+    )]
+    pub arena: Account<'info, Arena>,
+
+    /// Committee member, or listed relayer, submitting the result
+    #[account(constraint = arena.may_relay(&oracle.key()) @ SoliseumError::UnauthorizedOracle)]
+    pub oracle: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitGlobalConfig<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + GlobalConfig::LEN,
+        seeds = [b"config"],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(oracle: Pubkey)]
+pub struct InitOracleStats<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + OracleStats::LEN,
+        seeds = [b"oracle_stats", oracle.as_ref()],
+        bump
+    )]
+    pub oracle_stats: Account<'info, OracleStats>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_winner: u8, oracle_signatures: Vec<OracleSignature>)]
+pub struct CorrectWinner<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Settled @ arena.status_error(ArenaStatus::Settled)
+    )]
+    pub arena: Account<'info, Arena>,
+
+    /// Committee member, or listed relayer, submitting the result
+    #[account(constraint = arena.may_relay(&oracle.key()) @ SoliseumError::UnauthorizedOracle)]
+    pub oracle: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_oracles: Vec<Pubkey>, oracle_signatures: Option<Vec<OracleSignature>>)]
+pub struct ProposeOracleUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+    )]
+    pub arena: Account<'info, Arena>,
+
+    /// Authority: creator, or a current committee member with signatures (validated in handler)
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteOracleUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+    )]
+    pub arena: Account<'info, Arena>,
+}
+
+#[derive(Accounts)]
+pub struct RotateOracleSeat<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+    )]
+    pub arena: Account<'info, Arena>,
+
+    /// Current holder of the seat (checked against `index` in handler)
+    pub oracle: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelOracleUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        has_one = creator @ SoliseumError::InvalidArenaState,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+    )]
+    pub arena: Account<'info, Arena>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeSettlement<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Disputable @ arena.status_error(ArenaStatus::Disputable)
+    )]
+    pub arena: Account<'info, Arena>,
+}
+
+#[derive(Accounts)]
+#[instruction(oracle_signatures: Option<Vec<OracleSignature>>)]
+pub struct DisputeSettlement<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Disputable @ arena.status_error(ArenaStatus::Disputable)
+    )]
+    pub arena: Account<'info, Arena>,
+
+    /// Authority: creator, or a committee member or listed relayer with signatures
+    /// (validated in handler)
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(oracle_signatures: Option<Vec<OracleSignature>>)]
+pub struct CancelArena<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Active @ arena.status_error(ArenaStatus::Active)
+    )]
+    pub arena: Account<'info, Arena>,
+
+    /// Authority: creator, or a committee member or listed relayer with signatures
+    /// (validated in handler)
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyCancel<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        has_one = creator @ SoliseumError::InvalidArenaState,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Active @ arena.status_error(ArenaStatus::Active)
+    )]
+    pub arena: Account<'info, Arena>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReward<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status != ArenaStatus::Disputable @ SoliseumError::ChallengeWindowOpen,
+        constraint = arena.status == ArenaStatus::Settled @ arena.status_error(ArenaStatus::Settled),
+        constraint = arena.stake_mint.is_none() @ SoliseumError::WrongStakeCurrency
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(mut, seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA, holds SOL only (no data) so System Program allows transfer from it
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Deserialized and address-checked in the handler via `load_stake`, which knows
+    /// the side seed; malformed data fails with CorruptedStake
+    #[account(mut)]
+    pub stake: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TryClaim<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.stake_mint.is_none() @ SoliseumError::WrongStakeCurrency
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(mut, seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA, holds SOL only (no data) so System Program allows transfer from it
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Deserialized and address-checked in the handler via `load_stake`
+    #[account(mut)]
+    pub stake: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CrankClaim<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status != ArenaStatus::Disputable @ SoliseumError::ChallengeWindowOpen,
+        constraint = arena.status == ArenaStatus::Settled @ arena.status_error(ArenaStatus::Settled),
+        constraint = arena.stake_mint.is_none() @ SoliseumError::WrongStakeCurrency
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(mut, seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA, holds SOL only (no data) so System Program allows transfer from it
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Deserialized and address-checked against `owner` in the handler via `load_stake`
+    #[account(mut)]
+    pub stake: UncheckedAccount<'info>,
+
+    /// CHECK: Only receives lamports; `load_stake` ties it to the stake PDA's seeds
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    /// Anyone may crank; pays the transaction fee and nothing else
+    pub cranker: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewardsMany<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SweepMyStakes<'info> {
+    /// Owner of every stake in the sweep; receives their rent
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewardToken<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Settled @ arena.status_error(ArenaStatus::Settled)
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA; only signs as authority of the payout token vault
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Deserialized and address-checked in the handler via `load_stake`, which knows
+    /// the side seed; malformed data fails with CorruptedStake
+    #[account(mut)]
+    pub stake: UncheckedAccount<'info>,
+
+    pub payout_mint: Account<'info, Mint>,
+
+    /// Pre-funded payout token account owned by the vault PDA
+    #[account(
+        mut,
+        associated_token::mint = payout_mint,
+        associated_token::authority = vault
+    )]
+    pub payout_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = payout_mint,
+        token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewardSpl<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Settled @ arena.status_error(ArenaStatus::Settled),
+        constraint = arena.stake_mint == Some(stake_mint.key()) @ SoliseumError::WrongStakeCurrency
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA; only signs as authority of the stake token vault
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Deserialized and address-checked in the handler via `load_stake`, which knows
+    /// the side seed; malformed data fails with CorruptedStake
+    #[account(mut)]
+    pub stake: UncheckedAccount<'info>,
+
+    pub stake_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = stake_mint,
+        associated_token::authority = vault
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct RefundStake<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Cancelled @ arena.status_error(ArenaStatus::Cancelled),
+        constraint = arena.stake_mint.is_none() @ SoliseumError::WrongStakeCurrency
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(mut, seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA, holds SOL only (no data) so System Program allows transfer from it
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Deserialized and address-checked in the handler via `load_stake`, which knows
+    /// the side seed; malformed data fails with CorruptedStake
+    #[account(mut)]
+    pub stake: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefundStakeSpl<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Cancelled @ arena.status_error(ArenaStatus::Cancelled),
+        constraint = arena.stake_mint == Some(stake_mint.key()) @ SoliseumError::WrongStakeCurrency
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA; only signs as authority of the stake token vault
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Deserialized and address-checked in the handler via `load_stake`, which knows
+    /// the side seed; malformed data fails with CorruptedStake
+    #[account(mut)]
+    pub stake: UncheckedAccount<'info>,
+
+    pub stake_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = stake_mint,
+        associated_token::authority = vault
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct DistributeRewards<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Settled @ arena.status_error(ArenaStatus::Settled),
+        constraint = arena.stake_mint.is_none() @ SoliseumError::WrongStakeCurrency
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(mut, seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA, holds SOL only (no data) so System Program allows transfer from it
+    pub vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Settled @ arena.status_error(ArenaStatus::Settled),
+        constraint = arena.stake_mint.is_none() @ SoliseumError::WrongStakeCurrency
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(mut, seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA, holds SOL only (no data) so System Program allows transfer from it
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut, address = arena.fee_recipient @ SoliseumError::InvalidArenaState)]
+    /// CHECK: Receives the withdrawn fees
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// Creator or fee recipient (validated in handler)
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferral<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Settled @ arena.status_error(ArenaStatus::Settled),
+        constraint = arena.stake_mint.is_none() @ SoliseumError::WrongStakeCurrency
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(mut, seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA, holds SOL only (no data) so System Program allows transfer from it
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Deserialized and address-checked in the handler via `load_stake`, which knows
+    /// the side seed; malformed data fails with CorruptedStake
+    #[account(mut)]
+    pub stake: UncheckedAccount<'info>,
+
+    /// CHECK: Owner of the referred stake
+    pub staker: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseStake<'info> {
+    /// Settled or Cancelled, unless the stake is from a round before a reset (see
+    /// `check_stake_closable`)
+    #[account(
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", arena.key().as_ref(), user.key().as_ref(), &[stake.side], &stake.epoch.to_le_bytes()],
+        bump,
+        constraint = stake.owner == user.key() @ SoliseumError::CorruptedStake,
+        close = user
+    )]
+    pub stake: Account<'info, Stake>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireArena<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        close = creator
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(mut, seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA, holds SOL only (no data) so System Program allows transfer from it
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut, address = arena.creator @ SoliseumError::InvalidArenaState)]
+    /// CHECK: Creator receives the arena's rent
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(mut, address = arena.fee_recipient @ SoliseumError::InvalidArenaState)]
+    /// CHECK: Receives the swept vault balance
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// Any keeper may pay for the cleanup
+    pub keeper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SweepUnclaimed<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Settled @ arena.status_error(ArenaStatus::Settled)
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(mut, seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA, holds SOL only (no data) so System Program allows transfer from it
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut, address = arena.fee_recipient @ SoliseumError::InvalidArenaState)]
+    /// CHECK: Receives the swept vault balance
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// Any keeper may pay for the sweep
+    pub keeper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseArena<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        has_one = creator @ SoliseumError::InvalidArenaState,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = matches!(arena.status, ArenaStatus::Settled | ArenaStatus::Cancelled)
+            @ SoliseumError::ArenaStillOpen,
+        close = creator
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(mut, seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA, holds SOL only (no data) so System Program allows transfer from it
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut, address = arena.fee_recipient @ SoliseumError::InvalidArenaState)]
+    /// CHECK: Receives the swept vault balance
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(arena_id: u64)]
+pub struct MigrateArena<'info> {
+    /// CHECK: An old layout cannot be loaded as `Account<Arena>` until it is resized; the
+    /// seeds tie it to the signing creator, and the handler checks owner, discriminator
+    /// and version by hand
+    #[account(
+        mut,
+        seeds = [b"arena", creator.key().as_ref(), &arena_id.to_le_bytes()],
+        bump
+    )]
+    pub arena: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AdminRefundStake<'info> {
+    #[account(
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        has_one = creator @ SoliseumError::InvalidArenaState,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Active @ arena.status_error(ArenaStatus::Active),
+        constraint = arena.stake_mint.is_none() @ SoliseumError::WrongStakeCurrency
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(mut, seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA, holds SOL only (no data) so System Program allows transfer from it
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", arena.key().as_ref(), owner.key().as_ref(), &[stake.side], &arena.epoch.to_le_bytes()],
+        bump,
+        constraint = stake.version == STAKE_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = stake.owner == owner.key() @ SoliseumError::CorruptedStake
+    )]
+    pub stake: Account<'info, Stake>,
+
+    /// CHECK: Receives the refund; the stake's seeds and `owner` field pin it to the staker
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct InitializeTournament<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Tournament::LEN,
+        seeds = [b"tournament", creator.key().as_ref(), &tournament_id.to_le_bytes()],
+        bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(
+        has_one = creator @ SoliseumError::InvalidArenaState,
+        constraint = first_arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion
+    )]
+    pub first_arena: Account<'info, Arena>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
-impl Arena {
-    // creator(32) + oracles(96) + threshold(1) + total_pool(8) + agent_a_pool(8) + agent_b_pool(8)
-    // + status(1) + winner(1+1 for Option) + fee_bps(2) + settlement_nonce(8)
-    pub const LEN: usize = 32 + 96 + 1 + 8 + 8 + 8 + 1 + 2 + 2 + 8;
-}
+#[derive(Accounts)]
+pub struct AdvanceRound<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.creator.as_ref(), &tournament.tournament_id.to_le_bytes()],
+        bump,
+        has_one = creator @ SoliseumError::InvalidArenaState
+    )]
+    pub tournament: Account<'info, Tournament>,
 
-#[account]
-pub struct Stake {
-    pub owner: Pubkey,
-    pub amount: u64,
-    pub side: u8,
-    pub claimed: bool,
-}
+    /// The round in play, which must have a result before the bracket moves on
+    #[account(
+        constraint = tournament.arenas.get(tournament.current_round as usize) == Some(&current_arena.key())
+            @ SoliseumError::InvalidTournamentArena,
+        constraint = current_arena.status == ArenaStatus::Settled @ current_arena.status_error(ArenaStatus::Settled)
+    )]
+    pub current_arena: Account<'info, Arena>,
 
-impl Stake {
-    pub const LEN: usize = 32 + 8 + 1 + 1;
+    #[account(
+        mut,
+        seeds = [b"arena", next_arena.creator.as_ref(), &next_arena.arena_id.to_le_bytes()],
+        bump,
+        has_one = creator @ SoliseumError::InvalidArenaState,
+        constraint = next_arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = next_arena.status == ArenaStatus::Active @ next_arena.status_error(ArenaStatus::Active)
+    )]
+    pub next_arena: Account<'info, Arena>,
+
+    pub creator: Signer<'info>,
 }
 
 #[derive(Accounts)]
-#[instruction(fee_bps: u16, oracle_pubkeys: [Pubkey; MAX_ORACLES])]
-pub struct InitializeArena<'info> {
+pub struct AbandonArena<'info> {
     #[account(
-        init,
-        payer = creator,
-        space = 8 + Arena::LEN,
-        seeds = [b"arena", creator.key().as_ref()],
-        bump
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        has_one = creator @ SoliseumError::InvalidArenaState,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Active @ arena.status_error(ArenaStatus::Active),
+        close = creator
     )]
     pub arena: Account<'info, Arena>,
 
-    /// Vault PDA: holds staked SOL only (0 bytes data) so System Program allows transfer from it on claim
-    /// CHECK: Validated by seeds; created with space 0 in instruction
-    #[account(mut, seeds = [b"vault", creator.key().as_ref()], bump)]
+    #[account(mut, seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA, holds SOL only (no data) so System Program allows transfer from it
     pub vault: UncheckedAccount<'info>,
 
     #[account(mut)]
@@ -513,108 +5233,195 @@ pub struct InitializeArena<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(amount: u64, side: u8)]
-pub struct PlaceStake<'info> {
+pub struct FundVaultRent<'info> {
     #[account(
         mut,
-        seeds = [b"arena", arena.creator.as_ref()],
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
         bump,
-        constraint = arena.status == ArenaStatus::Active @ SoliseumError::InvalidArenaState
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
     )]
     pub arena: Account<'info, Arena>,
 
-    #[account(mut, seeds = [b"vault", arena.creator.as_ref()], bump)]
+    #[account(mut, seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
     /// CHECK: Vault PDA, holds SOL only
     pub vault: UncheckedAccount<'info>,
 
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct HouseDeposit<'info> {
     #[account(
-        init_if_needed,
-        payer = user,
-        space = 8 + Stake::LEN,
-        seeds = [b"stake", arena.key().as_ref(), user.key().as_ref()],
-        bump
+        mut,
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        has_one = creator @ SoliseumError::InvalidArenaState,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Active @ arena.status_error(ArenaStatus::Active),
+        constraint = arena.stake_mint.is_none() @ SoliseumError::WrongStakeCurrency,
+        constraint = arena.payout_mint.is_none() @ SoliseumError::WrongPayoutCurrency
     )]
-    pub stake: Account<'info, Stake>,
+    pub arena: Account<'info, Arena>,
+
+    #[account(mut, seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA, holds SOL only
+    pub vault: UncheckedAccount<'info>,
 
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub creator: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(oracle_signatures: Option<Vec<OracleSignature>>)]
-pub struct ResetArena<'info> {
+pub struct ReclaimHouseBonus<'info> {
     #[account(
         mut,
-        seeds = [b"arena", arena.creator.as_ref()],
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
         bump,
+        has_one = creator @ SoliseumError::InvalidArenaState,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = matches!(arena.status, ArenaStatus::Settled | ArenaStatus::Cancelled)
+            @ SoliseumError::ArenaStillOpen
     )]
     pub arena: Account<'info, Arena>,
 
-    #[account(mut, seeds = [b"vault", arena.creator.as_ref()], bump)]
-    /// CHECK: Vault PDA; we only check lamports == 0
+    #[account(mut, seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA, holds SOL only (no data) so System Program allows transfer from it
     pub vault: UncheckedAccount<'info>,
 
-    /// Authority: must be creator or one of the oracles (validated in handler)
-    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(winner: u8, oracle_signatures: Vec<OracleSignature>)]
-pub struct SettleGame<'info> {
+pub struct SeedLiquidity<'info> {
     #[account(
         mut,
-        seeds = [b"arena", arena.creator.as_ref()],
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
         bump,
+        has_one = creator @ SoliseumError::InvalidArenaState,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Active @ arena.status_error(ArenaStatus::Active),
+        constraint = arena.stake_mint.is_none() @ SoliseumError::WrongStakeCurrency
     )]
     pub arena: Account<'info, Arena>,
 
-    /// Must be one of the authorized oracles (signature validation in handler)
-    pub oracle: Signer<'info>,
+    #[account(mut, seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA, holds SOL only
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(new_oracles: [Pubkey; MAX_ORACLES], oracle_signatures: Option<Vec<OracleSignature>>)]
-pub struct UpdateOracles<'info> {
+pub struct SetFeeBps<'info> {
     #[account(
         mut,
-        seeds = [b"arena", arena.creator.as_ref()],
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
         bump,
+        has_one = creator @ SoliseumError::InvalidArenaState,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Active @ arena.status_error(ArenaStatus::Active)
     )]
     pub arena: Account<'info, Arena>,
 
-    /// Authority: creator or oracle committee
-    pub authority: Signer<'info>,
+    pub creator: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct ClaimReward<'info> {
+pub struct SetPaused<'info> {
     #[account(
         mut,
-        seeds = [b"arena", arena.creator.as_ref()],
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
         bump,
-        constraint = arena.status == ArenaStatus::Settled @ SoliseumError::InvalidArenaState
+        has_one = creator @ SoliseumError::InvalidArenaState,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion,
+        constraint = arena.status == ArenaStatus::Active @ arena.status_error(ArenaStatus::Active)
     )]
     pub arena: Account<'info, Arena>,
 
-    #[account(mut, seeds = [b"vault", arena.creator.as_ref()], bump)]
-    /// CHECK: Vault PDA, holds SOL only (no data) so System Program allows transfer from it
-    pub vault: UncheckedAccount<'info>,
+    pub creator: Signer<'info>,
+}
 
+#[derive(Accounts)]
+pub struct GetOdds<'info> {
     #[account(
-        mut,
-        seeds = [b"stake", arena.key().as_ref(), user.key().as_ref()],
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
         bump,
-        constraint = stake.owner == user.key() @ SoliseumError::InvalidArenaState,
-        constraint = !stake.claimed @ SoliseumError::AlreadyClaimed
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion
     )]
-    pub stake: Account<'info, Stake>,
+    pub arena: Account<'info, Arena>,
+}
 
-    #[account(mut)]
-    pub user: Signer<'info>,
+#[derive(Accounts)]
+pub struct GetArenaSummary<'info> {
+    #[account(
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion
+    )]
+    pub arena: Account<'info, Arena>,
+}
 
-    pub system_program: Program<'info, System>,
+#[derive(Accounts)]
+pub struct QuotePayout<'info> {
+    #[account(
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion
+    )]
+    pub arena: Account<'info, Arena>,
+
+    /// CHECK: Deserialized and address-checked in the handler via `load_stake`, which knows
+    /// the side seed; malformed data fails with CorruptedStake
+    pub stake: UncheckedAccount<'info>,
+
+    /// CHECK: Owner of the stake being quoted; need not sign a read-only view
+    pub user: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetClaimable<'info> {
+    #[account(
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA; only its lamport balance is read
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Deserialized and address-checked in the handler via `load_stake`, which knows
+    /// the side seed; malformed data fails with CorruptedStake
+    pub stake: UncheckedAccount<'info>,
+
+    /// CHECK: Owner of the stake being checked; need not sign a read-only view
+    pub user: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CheckInvariants<'info> {
+    #[account(
+        seeds = [b"arena", arena.creator.as_ref(), &arena.arena_id.to_le_bytes()],
+        bump,
+        constraint = arena.version == ARENA_VERSION @ SoliseumError::UnsupportedVersion
+    )]
+    pub arena: Account<'info, Arena>,
+
+    #[account(seeds = [b"vault", arena.key().as_ref()], bump = arena.vault_bump)]
+    /// CHECK: Vault PDA; only its lamport balance is read
+    pub vault: UncheckedAccount<'info>,
 }
 
 #[error_code]
@@ -631,7 +5438,7 @@ pub enum SoliseumError {
     #[msg("Invalid arena state for this operation")]
     InvalidArenaState,
 
-    #[msg("Insufficient oracle signatures (requires 2-of-3)")]
+    #[msg("Insufficient oracle signatures for the committee threshold")]
     InsufficientSignatures,
 
     #[msg("Duplicate oracle in signatures")]
@@ -645,4 +5452,426 @@ pub enum SoliseumError {
 
     #[msg("Invalid signature")]
     InvalidSignature,
+
+    #[msg("Arena has not been settled")]
+    ArenaNotSettled,
+
+    #[msg("Winning stakes are still unclaimed")]
+    ClaimsOutstanding,
+
+    #[msg("Vault still holds lamports")]
+    VaultNotEmpty,
+
+    #[msg("Stake amount is not a multiple of the arena's stake increment")]
+    InvalidStakeIncrement,
+
+    #[msg("Fee tiers must be ascending and within the maximum fee")]
+    InvalidFeeTiers,
+
+    #[msg("Stake account data is malformed or does not belong to this user")]
+    CorruptedStake,
+
+    #[msg("Distribution batch must be (stake, owner) pairs within the chunk limit")]
+    InvalidDistributionBatch,
+
+    #[msg("Stake does not match the distribution cursor")]
+    DistributionOutOfOrder,
+
+    #[msg("Payout token configuration is invalid")]
+    InvalidPayoutConfig,
+
+    #[msg("This arena pays out in a different currency")]
+    WrongPayoutCurrency,
+
+    #[msg("Payout token vault cannot cover this claim")]
+    PayoutVaultUnderfunded,
+
+    #[msg("Dispute window is still open")]
+    DisputeWindowOpen,
+
+    #[msg("Dispute window has closed or is not configured")]
+    DisputeWindowClosed,
+
+    #[msg("Claims were already paid to the settled winner")]
+    ClaimsAlreadyPaid,
+
+    #[msg("Claim expiry has not passed yet")]
+    ClaimWindowOpen,
+
+    #[msg("Implied odds moved below the caller's minimum")]
+    OddsSlippage,
+
+    #[msg("Account schema version is not supported by this program")]
+    UnsupportedVersion,
+
+    #[msg("Withdrawal exceeds the staked amount")]
+    WithdrawalExceedsStake,
+
+    #[msg("This arena takes stakes in a different currency")]
+    WrongStakeCurrency,
+
+    #[msg("Betting window is closed")]
+    BettingClosed,
+
+    #[msg("Betting window has not closed yet")]
+    BettingStillOpen,
+
+    #[msg("Stake has not been claimed or refunded yet")]
+    StakeNotClaimed,
+
+    #[msg("Stake is still needed by an unfinished distribution")]
+    DistributionPending,
+
+    #[msg("Stake is below the arena's minimum")]
+    StakeTooSmall,
+
+    #[msg("Stake would exceed the arena's per-user maximum")]
+    StakeLimitExceeded,
+
+    #[msg("Agent names must not be empty")]
+    InvalidAgentName,
+
+    #[msg("Outcome count must be between 2 and MAX_OUTCOMES")]
+    InvalidOutcomeCount,
+
+    #[msg("Arena is paused; new stakes are not accepted")]
+    ArenaPaused,
+
+    #[msg("An oracle committee update is already pending")]
+    OracleUpdatePending,
+
+    #[msg("No oracle committee update is pending")]
+    NoPendingOracleUpdate,
+
+    #[msg("Oracle committee update is still timelocked")]
+    OracleUpdateTimelocked,
+
+    #[msg("Emergency cancellation is not available until the oracle timeout has passed")]
+    EmergencyTimeoutPending,
+
+    #[msg("Claim batch must be (arena, vault, stake) triples within the batch limit")]
+    InvalidClaimBatch,
+
+    #[msg("referral_bps must not exceed fee_bps")]
+    InvalidReferralConfig,
+
+    #[msg("Invalid referrer for this stake")]
+    InvalidReferrer,
+
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+
+    #[msg("Referral cut has not been claimed yet")]
+    ReferralUnclaimed,
+
+    #[msg("Stake would push its side past the arena's maximum pool share")]
+    SideShareExceeded,
+
+    #[msg("The claim deadline has passed")]
+    ClaimExpired,
+
+    #[msg("Staking has already begun on this arena")]
+    StakingStarted,
+
+    #[msg("Oracle stats account does not belong to a signer of this result")]
+    InvalidOracleStats,
+
+    #[msg("Not enough participants to settle this arena")]
+    NotEnoughParticipants,
+
+    #[msg("Staker is not on this arena's allowlist")]
+    NotWhitelisted,
+
+    #[msg("Settlement is still inside its challenge window")]
+    ChallengeWindowOpen,
+
+    #[msg("Challenge window has closed")]
+    ChallengeWindowClosed,
+
+    #[msg("Amount exceeds the fees accrued so far")]
+    InsufficientAccruedFees,
+
+    #[msg("Stake would push the arena past its total pool cap")]
+    PoolCapExceeded,
+
+    #[msg("Account is not the GlobalConfig PDA")]
+    InvalidGlobalConfig,
+
+    #[msg("Stake is not on the settled winning side")]
+    NotAWinner,
+
+    #[msg("Oracle threshold must be between 1 and the committee size")]
+    InvalidThreshold,
+
+    #[msg("Transfer would dip into the vault's rent-exempt reserve")]
+    RentReserveBreached,
+
+    #[msg("Arena creator may not stake in this arena")]
+    CreatorStakeForbidden,
+
+    #[msg("Claim exceeds the stake's remaining payout")]
+    ClaimExceedsPayout,
+
+    #[msg("Settlement is locked out until the latest stake has aged")]
+    SettlementLockedOut,
+
+    #[msg("More oracle signatures than committee members")]
+    TooManySignatures,
+
+    #[msg("Vault balance is below the amount owed")]
+    VaultUnderfunded,
+
+    #[msg("Signing oracles do not span enough operator groups")]
+    InsufficientSignerDiversity,
+
+    #[msg("Stake is still within its withdrawal lock")]
+    StakeLocked,
+
+    #[msg("Claim recipient is missing, mismatched, or not a plain writable account")]
+    InvalidRecipient,
+
+    #[msg("Arena is not accepting stakes or settlement")]
+    ArenaNotActive,
+
+    #[msg("Arena has already been settled")]
+    ArenaAlreadySettled,
+
+    #[msg("Arena has not been cancelled")]
+    ArenaNotCancelled,
+
+    #[msg("Arena is not awaiting finalization")]
+    ArenaNotDisputable,
+
+    #[msg("Arena is neither settled nor cancelled")]
+    ArenaStillOpen,
+
+    #[msg("Settle batch needs one settlement per arena within the batch limit")]
+    InvalidSettleBatch,
+
+    #[msg("Signed settlement nonce does not match the arena; refetch and re-sign")]
+    StaleNonce,
+
+    #[msg("Every side must reach min_side_liquidity before settlement")]
+    MarketNotReady,
+
+    #[msg("Arena has been reset the maximum number of times")]
+    ResetLimitReached,
+
+    #[msg("Tournament already has the maximum number of rounds")]
+    TournamentFull,
+
+    #[msg("Arena is not the tournament's current round, or is already part of it")]
+    InvalidTournamentArena,
+
+    #[msg("Side's implied probability after this stake exceeds the caller's maximum")]
+    OddsSlippageExceeded,
+
+    #[msg("Sweep must be (arena, stake) pairs within the batch limit")]
+    InvalidSweepBatch,
+
+    #[msg("House bonus belongs to the winners of this arena")]
+    HouseBonusOwed,
+
+    #[msg("Stake belongs to an earlier round of this arena")]
+    StaleStake,
+
+    #[msg("House bonus must be reclaimed by the creator first")]
+    HouseBonusUnreclaimed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compute_payout_splits_the_net_loser_pool_pro_rata() {
+        // 2.5% of a 2 SOL loser pool is withheld; the 1 SOL stake owns a third of the winners
+        let payout = compute_payout(1_000_000_000, 3_000_000_000, 2_000_000_000, 250).unwrap();
+        assert_eq!(payout, 1_000_000_000 + 650_000_000);
+    }
+
+    #[test]
+    fn compute_payout_returns_the_stake_when_nobody_lost() {
+        assert_eq!(compute_payout(500, 500, 0, 250).unwrap(), 500);
+    }
+
+    #[test]
+    fn compute_payout_returns_only_the_stake_at_a_full_fee() {
+        assert_eq!(net_loser_pool(2_000_000_000, MAX_FEE_BPS).unwrap(), 0);
+        assert_eq!(compute_payout(1_000_000_000, 3_000_000_000, 2_000_000_000, MAX_FEE_BPS).unwrap(), 1_000_000_000);
+    }
+
+    #[test]
+    fn compute_payout_rejects_an_empty_winner_pool() {
+        assert!(compute_payout(0, 0, 1_000, 250).is_err());
+    }
+
+    #[test]
+    fn compute_payout_rejects_a_payout_above_u64() {
+        assert!(compute_payout(u64::MAX, 1, u64::MAX, 0).is_err());
+    }
+
+    #[test]
+    fn implied_probabilities_always_sum_to_the_full_book() {
+        let sum = |odds: Vec<u16>| odds.iter().map(|&bps| bps as u64).sum::<u64>();
+        assert_eq!(implied_probabilities_bps(&[0, 0], 0).unwrap(), vec![5_000, 5_000]);
+        assert_eq!(implied_probabilities_bps(&[0, 0, 0], 0).unwrap(), vec![3_334, 3_333, 3_333]);
+        assert_eq!(implied_probabilities_bps(&[1, 2], 3).unwrap(), vec![3_333, 6_667]);
+        assert_eq!(implied_probabilities_bps(&[5, 0], 5).unwrap(), vec![10_000, 0]);
+        let skewed = implied_probabilities_bps(&[u64::MAX / 3, 7, 11], u64::MAX / 3 + 18).unwrap();
+        assert_eq!(sum(skewed), 10_000);
+    }
+
+    #[test]
+    fn mul_div_keeps_the_product_in_u128_and_rounds_down() {
+        assert_eq!(mul_div(7, 3, 2).unwrap(), 10);
+        assert_eq!(mul_div(u64::MAX, u64::MAX, u64::MAX).unwrap(), u64::MAX);
+        assert_eq!(mul_div(u64::MAX, 2, 2).unwrap(), u64::MAX);
+        assert!(mul_div(u64::MAX, 2, 1).is_err());
+        assert!(mul_div(1, 1, 0).is_err());
+    }
+
+    #[test]
+    fn to_u64_rejects_values_past_the_max() {
+        assert_eq!(to_u64(u64::MAX as u128).unwrap(), u64::MAX);
+        assert!(to_u64(u64::MAX as u128 + 1).is_err());
+    }
+
+    #[test]
+    fn isqrt_floors_the_square_root() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(1), 1);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(1_000_000_000), 31_622);
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+    }
+
+    #[test]
+    fn quadratic_payout_damps_large_stakes_and_never_overpays() {
+        // 100 and 10_000 lamports split 1_100 by roots 10 and 100
+        let (small, whale) = (100, 10_000);
+        let sqrt_pool = isqrt(small) + isqrt(whale);
+        assert_eq!(quadratic_payout(small, sqrt_pool, 1_100).unwrap(), small + 100);
+        assert_eq!(quadratic_payout(whale, sqrt_pool, 1_100).unwrap(), whale + 1_000);
+        assert!(payout_from_net_pool(small, small + whale, 1_100).unwrap() < small + 100);
+
+        let stakes = [3u64, 7, 1_000_003];
+        let sqrt_pool: u64 = stakes.iter().map(|&s| isqrt(s)).sum();
+        let rewards: u64 = stakes
+            .iter()
+            .map(|&s| quadratic_payout(s, sqrt_pool, 999_999).unwrap() - s)
+            .sum();
+        assert!(rewards <= 999_999);
+        assert!(quadratic_payout(1, 0, 10).is_err());
+    }
+
+    #[test]
+    fn compute_payout_handles_full_pools_without_intermediate_overflow() {
+        let half = u64::MAX / 2;
+        assert_eq!(compute_payout(half, half, half, 0).unwrap(), u64::MAX - 1);
+    }
+
+    // The message tests below cover the bytes oracles sign, not signature checking, which
+    // `verify_ed25519_signature` does not do yet
+
+    #[test]
+    fn signed_messages_are_bound_to_the_program_id() {
+        let arena = Pubkey::new_unique();
+        let (devnet_program, other_program) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert_ne!(
+            create_settlement_message(&devnet_program, 0, &arena, 0, 1, &[0; 32]),
+            create_settlement_message(&other_program, 0, &arena, 0, 1, &[0; 32])
+        );
+        assert_ne!(
+            create_draw_message(&devnet_program, 0, &arena, 1),
+            create_draw_message(&other_program, 0, &arena, 1)
+        );
+        assert_ne!(
+            create_correction_message(&devnet_program, 0, &arena, 0, 1, 1),
+            create_correction_message(&other_program, 0, &arena, 0, 1, 1)
+        );
+    }
+
+    #[test]
+    fn signed_messages_are_bound_to_the_cluster_id() {
+        let (program_id, arena) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert_ne!(
+            create_settlement_message(&program_id, 0, &arena, 0, 1, &[0; 32]),
+            create_settlement_message(&program_id, 1, &arena, 0, 1, &[0; 32])
+        );
+        assert_ne!(
+            create_reset_message(&program_id, 0, &arena, 1),
+            create_reset_message(&program_id, 1, &arena, 1)
+        );
+        assert_ne!(
+            create_cancel_message(&program_id, 0, &arena, 1),
+            create_cancel_message(&program_id, 1, &arena, 1)
+        );
+        assert_ne!(
+            create_oracle_update_message(&program_id, 0, &arena, &[arena], 1),
+            create_oracle_update_message(&program_id, 1, &arena, &[arena], 1)
+        );
+        assert_ne!(
+            create_seat_rotation_message(&program_id, 0, &arena, 0, &arena, &program_id, 1),
+            create_seat_rotation_message(&program_id, 1, &arena, 0, &arena, &program_id, 1)
+        );
+    }
+
+    #[test]
+    fn settlement_message_layout_puts_the_domain_before_the_arena() {
+        let (program_id, arena) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let msg = create_settlement_message(&program_id, 7, &arena, 1, 9, &[0; 32]);
+        let tag = b"soliseum:settle:".len();
+        assert_eq!(&msg[..tag], b"soliseum:settle:");
+        assert_eq!(&msg[tag..tag + 32], program_id.as_ref());
+        assert_eq!(msg[tag + 32], 7);
+        assert_eq!(&msg[tag + 33..tag + 65], arena.as_ref());
+        assert_eq!(msg[tag + 65], 1);
+        assert_eq!(&msg[tag + 66..], &9u64.to_le_bytes());
+    }
+
+    #[test]
+    fn settlement_message_binds_the_outcome_hash() {
+        let (program_id, arena) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let legacy = create_settlement_message(&program_id, 0, &arena, 1, 9, &[0; 32]);
+        let hashed = create_settlement_message(&program_id, 0, &arena, 1, 9, &[7; 32]);
+        assert_eq!(&hashed[..legacy.len()], &legacy[..]);
+        assert_eq!(&hashed[legacy.len()..], &[7; 32]);
+        assert_ne!(hashed, create_settlement_message(&program_id, 0, &arena, 1, 9, &[8; 32]));
+    }
+
+    #[test]
+    fn outcome_parses_sides_and_draws_within_the_arena() {
+        assert_eq!(Outcome::from_winner(0, 2), Some(Outcome::AGENT_A));
+        assert_eq!(Outcome::from_winner(1, 2), Some(Outcome::AGENT_B));
+        assert_eq!(Outcome::from_winner(DRAW, 2), Some(Outcome::Draw));
+        assert_eq!(Outcome::from_winner(2, 2), None);
+        assert_eq!(Outcome::from_winner(2, 3), Some(Outcome::Side(2)));
+
+        assert_eq!(Outcome::from_side(1, 2), Some(Outcome::AGENT_B));
+        assert_eq!(Outcome::from_side(DRAW, 2), None);
+        assert_eq!(Outcome::from_side(7, 2), None);
+    }
+
+    #[test]
+    fn outcome_round_trips_through_its_raw_byte() {
+        for outcome in [Outcome::AGENT_A, Outcome::AGENT_B, Outcome::Side(7), Outcome::Draw] {
+            assert_eq!(Outcome::from_winner(outcome.to_u8(), MAX_OUTCOMES as u8), Some(outcome));
+        }
+        assert_eq!(Outcome::Draw.to_u8(), DRAW);
+    }
+
+    #[test]
+    fn allowlist_proof_accepts_members_and_rejects_outsiders() {
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let leaves = members.map(|pk| hashv(&[pk.as_ref()]).to_bytes());
+        let (lo, hi) = if leaves[0] <= leaves[1] { (leaves[0], leaves[1]) } else { (leaves[1], leaves[0]) };
+        let root = hashv(&[lo.as_ref(), hi.as_ref()]).to_bytes();
+
+        assert!(verify_allowlist_proof(&root, &members[0], &[leaves[1]]));
+        assert!(verify_allowlist_proof(&root, &members[1], &[leaves[0]]));
+        assert!(!verify_allowlist_proof(&root, &Pubkey::new_unique(), &[leaves[0]]));
+        assert!(!verify_allowlist_proof(&root, &members[0], &[]));
+    }
 }
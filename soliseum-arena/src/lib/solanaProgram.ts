@@ -24,8 +24,8 @@ function getClaimRewardDiscriminator(): Buffer {
   return Buffer.from([149, 95, 181, 242, 94, 90, 158, 162]);
 }
 
-/** Arena account layout: 8(disc) + creator(32) + ... */
-const ARENA_CREATOR_OFFSET = 8;
+/** Arena account layout: 8(disc) + version(1) + creator(32) + arena_id(8) + ... */
+const ARENA_CREATOR_OFFSET = 9;
 
 /** Fetch arena creator from on-chain arena account */
 export async function fetchArenaCreator(
@@ -39,34 +39,107 @@ export async function fetchArenaCreator(
   return new PublicKey(accountInfo.data.subarray(ARENA_CREATOR_OFFSET, ARENA_CREATOR_OFFSET + 32));
 }
 
-/** Derive vault PDA from creator */
-export function getVaultPda(creator: PublicKey): [PublicKey, number] {
+/**
+ * Read the arena's current round (`epoch`), which is part of every stake's PDA seeds.
+ * It follows several variable-length fields, so this walks the Borsh layout of `Arena`
+ * and must be kept in step with programs/soliseum/src/lib.rs.
+ */
+export function decodeArenaEpoch(data: Buffer): bigint {
+  let offset = ARENA_CREATOR_OFFSET + 32 + 8;
+  const skip = (bytes: number) => {
+    offset += bytes;
+  };
+  const vec = (elemBytes: number) => {
+    offset += 4 + data.readUInt32LE(offset) * elemBytes;
+  };
+  const option = (innerBytes: number) => {
+    offset += 1 + (data[offset] === 1 ? innerBytes : 0);
+  };
+
+  vec(32); // oracles
+  skip(1 + 8 + 1); // oracle_threshold, total_pool, outcomes
+  vec(8); // pools
+  skip(1); // status
+  option(1); // winner
+  skip(2 + 8); // fee_bps, settlement_nonce
+  vec(4); // stakers
+  skip(4 + 8); // unclaimed_count, stake_increment
+  vec(8 + 2); // fee_tiers
+  skip(4 + 1); // distribution_cursor, fully_distributed
+  option(32); // payout_mint
+  skip(8 + 8 + 8 + 32 + 8); // payout_rate .. claim_expiry_secs
+  option(32); // stake_mint
+  skip(6 * 8 + 32 + 32 + 16 + 1 + 8); // betting_opens_at .. oracle_update_delay_secs
+  vec(32); // pending_oracles
+  skip(8 + 8 + 8 + 2 + 8 + 8 + 8 + 1 + 2 + 8 + 8 + 1 + 1 + 8 + 2); // .. min_participants
+  vec(8); // seeded
+  skip(32 + 8 + 8 + 8 + 32 + 1 + 8 + 8 + 1); // whitelist_root .. payout_mode
+  vec(8); // sqrt_pools
+  skip(8 + 7 + 1 + 8 + 8 + 8 + 1 + 4 + 4 + 8 + 2); // settled_sqrt_pool .. settled_fee_bps
+  vec(32); // relayers
+  skip(2 + 8); // house_bonus_bps, house_bonus
+  return data.readBigUInt64LE(offset);
+}
+
+/** Fetch the arena's current round from on-chain arena account */
+export async function fetchArenaEpoch(
+  connection: Connection,
+  arenaAddress: PublicKey
+): Promise<bigint> {
+  const accountInfo = await connection.getAccountInfo(arenaAddress);
+  if (!accountInfo?.data) {
+    throw new Error("Invalid arena account");
+  }
+  return decodeArenaEpoch(Buffer.from(accountInfo.data));
+}
+
+/** Derive arena PDA from creator and the creator's arena id */
+export function getArenaPda(creator: PublicKey, arenaId = 0n): [PublicKey, number] {
+  const id = Buffer.alloc(8);
+  id.writeBigUInt64LE(arenaId);
+  return PublicKey.findProgramAddressSync([Buffer.from("arena"), creator.toBuffer(), id], PROGRAM_ID);
+}
+
+/** Derive vault PDA from arena */
+export function getVaultPda(arena: PublicKey): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
-    [Buffer.from("vault"), creator.toBuffer()],
+    [Buffer.from("vault"), arena.toBuffer()],
     PROGRAM_ID
   );
 }
 
-/** Derive stake PDA from arena and user */
-export function getStakePda(arena: PublicKey, user: PublicKey): [PublicKey, number] {
+/** Derive stake PDA from arena, user, the side staked on and the arena's round */
+export function getStakePda(
+  arena: PublicKey,
+  user: PublicKey,
+  side: number,
+  epoch: bigint
+): [PublicKey, number] {
+  const epochBytes = Buffer.alloc(8);
+  epochBytes.writeBigUInt64LE(epoch);
   return PublicKey.findProgramAddressSync(
-    [Buffer.from("stake"), arena.toBuffer(), user.toBuffer()],
+    [Buffer.from("stake"), arena.toBuffer(), user.toBuffer(), Buffer.from([side]), epochBytes],
     PROGRAM_ID
   );
 }
 
-/** Stake account layout: 8(disc) + owner(32) + amount(8) + side(1) + claimed(1) */
+/**
+ * Stake account layout: 8(disc) + version(1) + owner(32) + amount(8) + side(1) + claimed(1).
+ * Looks up the user's stake on either side of the arena's current round.
+ */
 export async function fetchStakeFromChain(
   connection: Connection,
   arenaAddress: PublicKey,
   user: PublicKey
 ): Promise<{ amount: number; side: number; claimed: boolean } | null> {
-  const [stakePda] = getStakePda(arenaAddress, user);
-  const info = await connection.getAccountInfo(stakePda);
-  if (!info?.data || info.data.length < 50) return null;
-  const amount = info.data.readBigUInt64LE(40);
-  const side = info.data.readUInt8(48);
-  const claimed = info.data.readUInt8(49) === 1;
+  const epoch = await fetchArenaEpoch(connection, arenaAddress);
+  const stakePdas = [0, 1].map((side) => getStakePda(arenaAddress, user, side, epoch)[0]);
+  const infos = await connection.getMultipleAccountsInfo(stakePdas);
+  const info = infos.find((account) => account?.data && account.data.length >= 51);
+  if (!info) return null;
+  const amount = info.data.readBigUInt64LE(41);
+  const side = info.data.readUInt8(49);
+  const claimed = info.data.readUInt8(50) === 1;
   return { amount: Number(amount), side, claimed };
 }
 
@@ -78,9 +151,9 @@ export async function buildPlaceStakeInstruction(
   amountLamports: bigint,
   side: 0 | 1
 ): Promise<TransactionInstruction> {
-  const creator = await fetchArenaCreator(connection, arenaAddress);
-  const [vaultPda] = getVaultPda(creator);
-  const [stakePda] = getStakePda(arenaAddress, user);
+  const epoch = await fetchArenaEpoch(connection, arenaAddress);
+  const [vaultPda] = getVaultPda(arenaAddress);
+  const [stakePda] = getStakePda(arenaAddress, user, side, epoch);
 
   const data = Buffer.alloc(8 + 8 + 1);
   getPlaceStakeDiscriminator().copy(data, 0);
@@ -104,14 +177,17 @@ export async function buildPlaceStakeInstruction(
 export async function buildClaimRewardInstruction(
   connection: Connection,
   arenaAddress: PublicKey,
-  user: PublicKey
+  user: PublicKey,
+  side: 0 | 1
 ): Promise<TransactionInstruction> {
-  const creator = await fetchArenaCreator(connection, arenaAddress);
-  const [vaultPda] = getVaultPda(creator);
-  const [stakePda] = getStakePda(arenaAddress, user);
+  const epoch = await fetchArenaEpoch(connection, arenaAddress);
+  const [vaultPda] = getVaultPda(arenaAddress);
+  const [stakePda] = getStakePda(arenaAddress, user, side, epoch);
 
-  const data = Buffer.alloc(8);
+  // discriminator + recipient: Option<Pubkey> (None pays the signer)
+  const data = Buffer.alloc(9);
   getClaimRewardDiscriminator().copy(data, 0);
+  data.writeUInt8(0, 8);
 
   return new TransactionInstruction({
     programId: PROGRAM_ID,
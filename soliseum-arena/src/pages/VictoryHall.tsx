@@ -212,10 +212,12 @@ export default function VictoryHall() {
     setClaimStatus("pending");
     const toastId = toast.loading("Confirm claim in your wallet...");
     try {
+      // Only winners claim, and the stake's address depends on the side it backed
       const ix = await buildClaimRewardInstruction(
         connection,
         new PublicKey(battleId),
-        publicKey
+        publicKey,
+        (effectiveStake?.side ?? winnerSide ?? 0) as 0 | 1
       );
       const { Transaction } = await import("@solana/web3.js");
       const tx = new Transaction().add(ix);
@@ -248,6 +250,8 @@ export default function VictoryHall() {
     connected,
     publicKey,
     battleId,
+    effectiveStake?.side,
+    winnerSide,
     connection,
     sendTransaction,
     setWalletModalVisible,
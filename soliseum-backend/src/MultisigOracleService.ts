@@ -46,76 +46,80 @@ export interface ArenaMultisigState {
 }
 
 /**
- * Creates deterministic settlement message for signing
- * Format: "soliseum:settle:" + arena_pubkey (32 bytes) + winner (1 byte) + nonce (8 bytes LE)
+ * Cluster tag signed into every oracle message; must match the arena's `cluster_id`
  */
-function createSettlementMessage(
+const CLUSTER_ID = Number(process.env.ORACLE_CLUSTER_ID ?? 0);
+
+/**
+ * Shared message head: prefix + program id (32 bytes) + cluster id (1 byte) + arena_pubkey (32 bytes),
+ * followed by `body` and the nonce (8 bytes LE)
+ */
+function createMessage(
+  tag: string,
   arenaAddress: PublicKey,
-  winner: number,
+  body: Uint8Array,
   nonce: bigint
 ): Uint8Array {
-  const prefix = new TextEncoder().encode("soliseum:settle:");
-  const winnerByte = new Uint8Array([winner]);
+  const prefix = new TextEncoder().encode(tag);
   const nonceBytes = new Uint8Array(8);
   const view = new DataView(nonceBytes.buffer);
   view.setBigUint64(0, nonce, true); // Little-endian
-  
-  const message = new Uint8Array(prefix.length + 32 + 1 + 8);
+
+  const message = new Uint8Array(prefix.length + 65 + body.length + 8);
   message.set(prefix, 0);
-  message.set(arenaAddress.toBytes(), prefix.length);
-  message.set(winnerByte, prefix.length + 32);
-  message.set(nonceBytes, prefix.length + 33);
-  
+  message.set(PROGRAM_ID.toBytes(), prefix.length);
+  message.set([CLUSTER_ID], prefix.length + 32);
+  message.set(arenaAddress.toBytes(), prefix.length + 33);
+  message.set(body, prefix.length + 65);
+  message.set(nonceBytes, prefix.length + 65 + body.length);
+
   return message;
 }
 
+/**
+ * Creates deterministic settlement message for signing
+ * Format: "soliseum:settle:" + program id + cluster id + arena_pubkey + winner (1 byte) + nonce
+ * + outcome hash (32 bytes, only when supplied and non-zero)
+ */
+function createSettlementMessage(
+  arenaAddress: PublicKey,
+  winner: number,
+  nonce: bigint,
+  outcomeHash?: Uint8Array
+): Uint8Array {
+  const message = createMessage("soliseum:settle:", arenaAddress, new Uint8Array([winner]), nonce);
+  if (!outcomeHash || outcomeHash.every((b) => b === 0)) {
+    return message;
+  }
+  const withHash = new Uint8Array(message.length + 32);
+  withHash.set(message, 0);
+  withHash.set(outcomeHash, message.length);
+  return withHash;
+}
+
 /**
  * Creates deterministic reset message for signing
- * Format: "soliseum:reset:" + arena_pubkey (32 bytes) + nonce (8 bytes LE)
+ * Format: "soliseum:reset:" + program id + cluster id + arena_pubkey + nonce
  */
 function createResetMessage(
   arenaAddress: PublicKey,
   nonce: bigint
 ): Uint8Array {
-  const prefix = new TextEncoder().encode("soliseum:reset:");
-  const nonceBytes = new Uint8Array(8);
-  const view = new DataView(nonceBytes.buffer);
-  view.setBigUint64(0, nonce, true);
-  
-  const message = new Uint8Array(prefix.length + 32 + 8);
-  message.set(prefix, 0);
-  message.set(arenaAddress.toBytes(), prefix.length);
-  message.set(nonceBytes, prefix.length + 32);
-  
-  return message;
+  return createMessage("soliseum:reset:", arenaAddress, new Uint8Array(0), nonce);
 }
 
 /**
  * Creates deterministic oracle update message
- * Format: "soliseum:update_oracles:" + arena_pubkey + new_oracles (3 * 32 bytes) + nonce
+ * Format: "soliseum:update_oracles:" + program id + cluster id + arena_pubkey + new_oracles (32 bytes each) + nonce
  */
 function createOracleUpdateMessage(
   arenaAddress: PublicKey,
   newOracles: PublicKey[],
   nonce: bigint
 ): Uint8Array {
-  const prefix = new TextEncoder().encode("soliseum:update_oracles:");
-  const nonceBytes = new Uint8Array(8);
-  const view = new DataView(nonceBytes.buffer);
-  view.setBigUint64(0, nonce, true);
-  
-  const message = new Uint8Array(prefix.length + 32 + 96 + 8);
-  message.set(prefix, 0);
-  message.set(arenaAddress.toBytes(), prefix.length);
-  
-  let offset = prefix.length + 32;
-  for (const oracle of newOracles) {
-    message.set(oracle.toBytes(), offset);
-    offset += 32;
-  }
-  
-  message.set(nonceBytes, offset);
-  return message;
+  const body = new Uint8Array(newOracles.length * 32);
+  newOracles.forEach((oracle, i) => body.set(oracle.toBytes(), i * 32));
+  return createMessage("soliseum:update_oracles:", arenaAddress, body, nonce);
 }
 
 /**
@@ -228,10 +232,11 @@ export class MultisigOracleService {
   createSettlementSignature(
     arenaAddress: PublicKey,
     winner: number,
-    nonce: bigint
+    nonce: bigint,
+    outcomeHash?: Uint8Array
 ): OracleSignature {
     const config = this.getThisNodeConfig();
-    const message = createSettlementMessage(arenaAddress, winner, nonce);
+    const message = createSettlementMessage(arenaAddress, winner, nonce, outcomeHash);
     const signature = nacl.sign.detached(message, config.privateKey);
     
     return {
@@ -280,12 +285,13 @@ export class MultisigOracleService {
     arenaAddress: PublicKey,
     winner: number,
     nonce: bigint,
-    state: ArenaMultisigState
+    state: ArenaMultisigState,
+    outcomeHash?: Uint8Array
   ): Promise<OracleSignature[]> {
     const signatures: OracleSignature[] = [];
     
     // Add this node's signature
-    signatures.push(this.createSettlementSignature(arenaAddress, winner, nonce));
+    signatures.push(this.createSettlementSignature(arenaAddress, winner, nonce, outcomeHash));
     
     // Collect from remote oracles
     for (let i = 0; i < 3; i++) {
@@ -305,6 +311,7 @@ export class MultisigOracleService {
             arenaAddress: arenaAddress.toBase58(),
             winner,
             nonce: nonce.toString(),
+            outcomeHash: outcomeHash ? Buffer.from(outcomeHash).toString("hex") : undefined,
             requester: this.getThisNodeConfig().publicKey.toBase58(),
           }),
           signal: AbortSignal.timeout(5000),
@@ -319,7 +326,7 @@ export class MultisigOracleService {
         const signatureBytes = bs58.decode(data.signature);
         
         // Verify signature before accepting
-        const message = createSettlementMessage(arenaAddress, winner, nonce);
+        const message = createSettlementMessage(arenaAddress, winner, nonce, outcomeHash);
         if (this.verifySignature(data.oracle_index, signatureBytes, message, state)) {
           signatures.push({
             oracle_index: data.oracle_index,
@@ -340,13 +347,18 @@ export class MultisigOracleService {
   /**
    * Encode settle_game instruction data with multisig signatures
    * Format: discriminator (8) + winner (1) + signatures_count (1) + [oracle_index (1) + signature (64)]*
+   * + outcome_hash option (1, then 32 when present) + expected_nonce (8, LE)
    */
   private encodeSettleGameMultisig(
     winner: number,
-    signatures: OracleSignature[]
+    signatures: OracleSignature[],
+    nonce: bigint,
+    outcomeHash?: Uint8Array
   ): Buffer {
     const discriminator = Buffer.from([134, 40, 71, 113, 16, 49, 57, 118]); // settle_game discriminator
-    const data = Buffer.alloc(discriminator.length + 1 + 1 + signatures.length * 65);
+    const data = Buffer.alloc(
+      discriminator.length + 1 + 1 + signatures.length * 65 + 1 + (outcomeHash ? 32 : 0) + 8
+    );
     
     discriminator.copy(data, 0);
     data.writeUInt8(winner, 8);
@@ -358,12 +370,21 @@ export class MultisigOracleService {
       Buffer.from(sig.signature).copy(data, offset + 1);
       offset += 65;
     }
+
+    if (outcomeHash) {
+      data.writeUInt8(1, offset);
+      Buffer.from(outcomeHash).copy(data, offset + 1);
+      offset += 32;
+    }
+    offset += 1;
+    data.writeBigUInt64LE(nonce, offset);
     
     return data;
   }
 
   /**
    * Encode reset_arena instruction data with optional multisig signatures
+   * Trailing None bytes keep the fee and betting window unchanged (only the creator may set them)
    */
   private encodeResetArenaMultisig(
     signatures?: OracleSignature[]
@@ -372,13 +393,13 @@ export class MultisigOracleService {
     
     if (!signatures || signatures.length === 0) {
       // Creator reset (no signatures needed)
-      const data = Buffer.alloc(discriminator.length + 1);
+      const data = Buffer.alloc(discriminator.length + 1 + 3);
       discriminator.copy(data, 0);
       data.writeUInt8(0, 8); // signature count = 0
       return data;
     }
     
-    const data = Buffer.alloc(discriminator.length + 1 + signatures.length * 65);
+    const data = Buffer.alloc(discriminator.length + 1 + signatures.length * 65 + 3);
     discriminator.copy(data, 0);
     data.writeUInt8(signatures.length, 8);
     
@@ -406,9 +427,13 @@ export class MultisigOracleService {
     options: {
       skipRemoteCollection?: boolean; // For testing - use only local signature
       maxRetries?: number;
+      outcomeHash?: Uint8Array; // 32-byte commitment to the match evidence, signed with the winner
     } = {}
   ): Promise<string> {
-    const { skipRemoteCollection = false, maxRetries = 3 } = options;
+    const { skipRemoteCollection = false, maxRetries = 3, outcomeHash } = options;
+    if (outcomeHash && outcomeHash.length !== 32) {
+      throw new Error(`Outcome hash must be 32 bytes (got ${outcomeHash.length})`);
+    }
     
     const arenaPubkey = new PublicKey(arenaAddress);
     const state = await this.getArenaMultisigState(arenaAddress);
@@ -430,8 +455,8 @@ export class MultisigOracleService {
     if (skipRemoteCollection) {
       // For testing: sign twice with same key (not valid for production)
       signatures = [
-        this.createSettlementSignature(arenaPubkey, winner, state.settlementNonce),
-        this.createSettlementSignature(arenaPubkey, winner, state.settlementNonce), // Duplicate for testing
+        this.createSettlementSignature(arenaPubkey, winner, state.settlementNonce, outcomeHash),
+        this.createSettlementSignature(arenaPubkey, winner, state.settlementNonce, outcomeHash), // Duplicate for testing
       ];
       signatures[1].oracle_index = (signatures[0].oracle_index + 1) % 3;
     } else {
@@ -439,7 +464,8 @@ export class MultisigOracleService {
         arenaPubkey,
         winner,
         state.settlementNonce,
-        state
+        state,
+        outcomeHash
       );
     }
 
@@ -466,7 +492,12 @@ export class MultisigOracleService {
     }
 
     // Build and send transaction
-    const data = this.encodeSettleGameMultisig(winner, uniqueSignatures.slice(0, state.threshold));
+    const data = this.encodeSettleGameMultisig(
+      winner,
+      uniqueSignatures.slice(0, state.threshold),
+      state.settlementNonce,
+      outcomeHash
+    );
     const keys = [
       { pubkey: arenaPubkey, isSigner: false, isWritable: true },
       { pubkey: thisConfig.publicKey, isSigner: true, isWritable: false },
@@ -577,12 +608,8 @@ export class MultisigOracleService {
     }
 
     // Get vault PDA
-    const info = await this.connection.getAccountInfo(arenaPubkey);
-    if (!info) throw new Error("Arena not found");
-    const creatorBytes = info.data.subarray(8, 40); // After discriminator
-    const creatorPubkey = new PublicKey(creatorBytes);
     const [vaultPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("vault"), creatorPubkey.toBuffer()],
+      [Buffer.from("vault"), arenaPubkey.toBuffer()],
       PROGRAM_ID
     );
 
@@ -35,7 +35,6 @@ const RETRY_BASE_DELAY_MS = 2000;
 //   [96]     — status: u8 (ArenaStatus enum)
 //   [97]     — winner: Option<u8> tag (0 = None, 1 = Some)
 //   [98]     — winner: Option<u8> value (0 = agent A, 1 = agent B)
-const OFFSET_CREATOR = 8;
 const OFFSET_STATUS = 96;
 const OFFSET_WINNER_TAG = 97;
 const OFFSET_WINNER_VALUE = 98;
@@ -115,7 +114,7 @@ export class SolanaService {
   }
 
   /** Arena status enum from program (must match lib.rs ArenaStatus). */
-  private static readonly ARENA_STATUS = { Pending: 0, Active: 1, Settled: 2, Cancelled: 3 } as const;
+  private static readonly ARENA_STATUS = { Pending: 0, Active: 1, Settled: 2, Cancelled: 3, Disputable: 4 } as const;
 
   /**
    * Acquire per-arena lock. Throws if another operation is already in progress.
@@ -255,11 +254,8 @@ export class SolanaService {
           `Arena must be Settled to reset (current status: ${status}). Only settled arenas can be reset.`
         );
       }
-      const creatorBytes = info.data.subarray(OFFSET_CREATOR, OFFSET_CREATOR + 32);
-      const creatorPubkey = new PublicKey(creatorBytes);
-
       const [vaultPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), creatorPubkey.toBuffer()],
+        [Buffer.from("vault"), arenaPubkey.toBuffer()],
         PROGRAM_ID
       );
       const vaultBalance = await this.connection.getBalance(vaultPda);
@@ -292,20 +288,21 @@ export class SolanaService {
    * Create a new arena on-chain.
    * Returns the arena PDA address.
    * 
-   * Note: The arena is seeded with the creator's pubkey, so each creator can only have one arena.
-   * For the matchmaking system, the oracle acts as the creator for all arenas.
+   * Note: The arena is seeded with the creator's pubkey and a per-creator arena id.
+   * For the matchmaking system, the oracle acts as the creator and uses arena id 0.
    * The arena will be reset after each battle and reused.
    */
   async createArenaOnChain(): Promise<{ arenaAddress: string; vaultAddress: string }> {
     const oracle = this.getOracle();
 
     // Derive PDA addresses
+    const arenaId = Buffer.alloc(8); // u64 LE, arena id 0
     const [arenaPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("arena"), oracle.publicKey.toBuffer()],
+      [Buffer.from("arena"), oracle.publicKey.toBuffer(), arenaId],
       PROGRAM_ID
     );
     const [vaultPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("vault"), oracle.publicKey.toBuffer()],
+      [Buffer.from("vault"), arenaPda.toBuffer()],
       PROGRAM_ID
     );
 
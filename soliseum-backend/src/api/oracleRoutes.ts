@@ -28,6 +28,7 @@ interface SignRequest {
   arenaAddress: string;
   winner: number;
   nonce: string; // BigInt as string
+  outcomeHash?: string; // Optional 32-byte hex commitment signed with the winner
   requester: string; // Oracle pubkey that is requesting
 }
 
@@ -122,6 +123,16 @@ export class OracleCoordinationAPI {
         return;
       }
 
+      // Validate outcome hash
+      let outcomeHash: Uint8Array | undefined;
+      if (body.outcomeHash !== undefined) {
+        if (!/^[0-9a-fA-F]{64}$/.test(body.outcomeHash)) {
+          res.status(400).json({ ok: false, error: "Invalid outcome hash (must be 32 bytes hex)" });
+          return;
+        }
+        outcomeHash = Buffer.from(body.outcomeHash, "hex");
+      }
+
       // Check replay
       const requestId = `${body.arenaAddress}:${body.winner}:${body.nonce}`;
       if (this.isReplay(requestId)) {
@@ -164,7 +175,8 @@ export class OracleCoordinationAPI {
       const signature = this.multisigService.createSettlementSignature(
         arenaPubkey,
         body.winner,
-        requestNonce
+        requestNonce,
+        outcomeHash
       );
 
       res.json({